@@ -0,0 +1,442 @@
+use proc_macro2::{Span, TokenStream};
+use syn::{
+    ext::IdentExt,
+    meta::ParseNestedMeta,
+    parenthesized,
+    parse::{Parse, ParseStream, Parser},
+    spanned::Spanned,
+    token::Paren,
+    Ident, LitInt, LitStr, Path, Token, Visibility,
+};
+
+use crate::zip;
+
+pub(crate) struct AttrArgs {
+    pub array_field_vis: Visibility,
+    pub flags: Flags,
+    pub consts: ConstsMode,
+    pub names: Names,
+
+    /// Path used by the `macro_rules` arg's macro to refer to the struct
+    /// e.g. `crate::color::Rgb`
+    pub macro_path: Option<Path>,
+
+    /// Element types to generate the `int_ops` arg's methods for.
+    /// All primitive integer types if empty.
+    pub int_types: Vec<Ident>,
+
+    /// Options of the `trymap` arg e.g. `trymap(alloc)`
+    pub trymap_opts: TrymapOpts,
+
+    /// Max arity of the `zip` arg's N-ary methods e.g. 3 for `zip3`.
+    /// 2 if not set, which only generates the 2-way methods.
+    pub zip_arity: usize,
+}
+
+/// Options of the `trymap` arg
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct TrymapOpts {
+    /// Also generate methods that allocate, e.g. `collect_errors`,
+    /// which refer to the `alloc` crate
+    pub alloc: bool,
+
+    /// Also generate methods that report the failing field, e.g. `try_map_res_named`
+    pub named: bool,
+
+    /// Also generate `try_map` over any `generic_array_struct_rt::TryLike`
+    pub generic: bool,
+}
+
+/// Where the `LEN` and `IDX_*` consts are output.
+/// They are always output as associated consts of the struct.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum ConstsMode {
+    /// Also output as free consts with prefix e.g. `RGB_LEN`, `RGB_IDX_R`
+    #[default]
+    Free,
+
+    /// Only output as associated consts
+    Assoc,
+
+    /// Also output in a module e.g. `rgb_idx::LEN`, `rgb_idx::R`
+    Mod,
+}
+
+// be pretty funny if this was a #[generic_array_struct]
+#[derive(Default)]
+pub(crate) struct Flags {
+    pub builder: bool,
+    pub destr: bool,
+    pub trymap: bool,
+    pub zip: bool,
+    pub macro_rules: bool,
+    pub ctor: bool,
+    pub field_enum: bool,
+    pub ops: bool,
+    pub int_ops: bool,
+    pub reduce: bool,
+    pub mask: bool,
+    pub fieldset: bool,
+    pub patch: bool,
+    pub r#async: bool,
+    pub soa: bool,
+    pub transpose: bool,
+}
+
+/// Overrides for the names of generated items
+#[derive(Default)]
+pub(crate) struct Names {
+    /// e.g. `"get_{}"`
+    pub getter: Option<LitStr>,
+
+    /// e.g. `"{}_mut"`
+    pub getter_mut: Option<LitStr>,
+
+    /// e.g. `"set_{}"`
+    pub setter: Option<LitStr>,
+
+    /// e.g. `"with_{}"`
+    pub with: Option<LitStr>,
+
+    /// e.g. `"const_with_{}"`
+    pub const_with: Option<LitStr>,
+
+    /// e.g. `RgbBuilder`
+    pub builder: Option<Ident>,
+
+    /// e.g. `NewRgbBuilder`
+    pub new_builder: Option<Ident>,
+
+    /// e.g. `RgbDestr`
+    pub destr: Option<Ident>,
+
+    /// e.g. `RGB` as in `RGB_LEN`
+    pub consts_prefix: Option<Ident>,
+
+    /// e.g. `rgb_idx` as in `rgb_idx::LEN`
+    pub consts_mod: Option<Ident>,
+
+    /// e.g. `rgb` as in `rgb!{ r: 0, g: 0, b: 0 }`
+    pub macro_rules: Option<Ident>,
+
+    /// e.g. `RgbField`
+    pub field_enum: Option<Ident>,
+
+    /// e.g. `RgbFieldSet`
+    pub fieldset: Option<Ident>,
+
+    /// e.g. `RgbVec`
+    pub soa: Option<Ident>,
+}
+
+/// Element types that the `int_ops` arg supports
+pub(crate) const INT_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+fn set_flag_checked(r: &mut bool, id: &Ident) -> syn::Result<()> {
+    if *r {
+        return Err(syn::Error::new(id.span(), format!("`{id}` already set")));
+    }
+    *r = true;
+    Ok(())
+}
+
+fn set_opt_checked<T>(r: &mut Option<T>, val: T, name: &str, span: Span) -> syn::Result<()> {
+    if r.is_some() {
+        return Err(syn::Error::new(span, format!("`{name}` already set")));
+    }
+    *r = Some(val);
+    Ok(())
+}
+
+/// Parses the optional `(key = val, ...)` that follows a flag arg
+/// e.g. `builder(name = RgbMaker)`
+fn parse_flag_opts(
+    input: ParseStream,
+    logic: impl FnMut(ParseNestedMeta) -> syn::Result<()>,
+) -> syn::Result<()> {
+    if !input.peek(Paren) {
+        return Ok(());
+    }
+    let content;
+    parenthesized!(content in input);
+    let content: TokenStream = content.parse()?;
+    syn::meta::parser(logic).parse2(content)
+}
+
+impl Parse for AttrArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut flags = Flags::default();
+        let mut names = Names::default();
+        let mut consts: Option<ConstsMode> = None;
+        let mut all: Option<Ident> = None;
+        // args enabled by `all` that were specified without options e.g. `zip` but not `zip(arity = 3)`
+        let mut bare_all_args: Vec<Ident> = Vec::new();
+        let mut macro_path: Option<Path> = None;
+        let mut int_types: Vec<Ident> = Vec::new();
+        let mut trymap_opts = TrymapOpts::default();
+        let mut zip_arity: Option<usize> = None;
+        let Flags {
+            builder,
+            destr,
+            trymap,
+            zip,
+            macro_rules,
+            ctor,
+            field_enum,
+            ops,
+            int_ops,
+            reduce,
+            mask,
+            fieldset,
+            patch,
+            r#async,
+            soa,
+            transpose,
+        } = &mut flags;
+
+        // `async` is a keyword, so is not an `Ident` to syn
+        while input.peek(Ident) || input.peek(Token![async]) {
+            let id = input.call(Ident::parse_any)?;
+            // cant match here, ident is not str
+            if id == "all" {
+                set_opt_checked(&mut all, id.clone(), "all", id.span())?;
+            } else if id == "builder" {
+                set_flag_checked(builder, &id)?;
+                if !input.peek(Paren) {
+                    bare_all_args.push(id.clone());
+                }
+                parse_flag_opts(input, |meta| {
+                    let span = meta.path.span();
+                    if meta.path.is_ident("name") {
+                        set_opt_checked(&mut names.builder, meta.value()?.parse()?, "name", span)
+                    } else if meta.path.is_ident("new") {
+                        set_opt_checked(&mut names.new_builder, meta.value()?.parse()?, "new", span)
+                    } else {
+                        Err(meta.error("Expected one of [`name`, `new`]"))
+                    }
+                })?;
+            } else if id == "destr" {
+                set_flag_checked(destr, &id)?;
+                if !input.peek(Paren) {
+                    bare_all_args.push(id.clone());
+                }
+                parse_flag_opts(input, |meta| {
+                    if meta.path.is_ident("name") {
+                        let span = meta.path.span();
+                        set_opt_checked(&mut names.destr, meta.value()?.parse()?, "name", span)
+                    } else {
+                        Err(meta.error("Expected `name`"))
+                    }
+                })?;
+            } else if id == "trymap" {
+                set_flag_checked(trymap, &id)?;
+                if !input.peek(Paren) {
+                    bare_all_args.push(id.clone());
+                }
+                parse_flag_opts(input, |meta| {
+                    if meta.path.is_ident("alloc") {
+                        set_flag_checked(&mut trymap_opts.alloc, meta.path.require_ident()?)
+                    } else if meta.path.is_ident("named") {
+                        set_flag_checked(&mut trymap_opts.named, meta.path.require_ident()?)
+                    } else if meta.path.is_ident("generic") {
+                        set_flag_checked(&mut trymap_opts.generic, meta.path.require_ident()?)
+                    } else {
+                        Err(meta.error("Expected one of [`alloc`, `named`, `generic`]"))
+                    }
+                })?;
+            } else if id == "zip" {
+                set_flag_checked(zip, &id)?;
+                if !input.peek(Paren) {
+                    bare_all_args.push(id.clone());
+                }
+                parse_flag_opts(input, |meta| {
+                    if meta.path.is_ident("arity") {
+                        let span = meta.path.span();
+                        let lit: LitInt = meta.value()?.parse()?;
+                        let arity: usize = lit.base10_parse()?;
+                        if !(2..=zip::MAX_ARITY).contains(&arity) {
+                            return Err(syn::Error::new(
+                                lit.span(),
+                                format!("`arity` must be between 2 and {}", zip::MAX_ARITY),
+                            ));
+                        }
+                        set_opt_checked(&mut zip_arity, arity, "arity", span)
+                    } else {
+                        Err(meta.error("Expected `arity`"))
+                    }
+                })?;
+            } else if id == "macro_rules" {
+                set_flag_checked(macro_rules, &id)?;
+                parse_flag_opts(input, |meta| {
+                    let span = meta.path.span();
+                    if meta.path.is_ident("name") {
+                        set_opt_checked(
+                            &mut names.macro_rules,
+                            meta.value()?.parse()?,
+                            "name",
+                            span,
+                        )
+                    } else if meta.path.is_ident("path") {
+                        set_opt_checked(&mut macro_path, meta.value()?.parse()?, "path", span)
+                    } else {
+                        Err(meta.error("Expected one of [`name`, `path`]"))
+                    }
+                })?;
+            } else if id == "ctor" {
+                set_flag_checked(ctor, &id)?;
+            } else if id == "ops" {
+                set_flag_checked(ops, &id)?;
+            } else if id == "int_ops" {
+                set_flag_checked(int_ops, &id)?;
+                parse_flag_opts(input, |meta| {
+                    let ty = meta.path.require_ident()?;
+                    if !INT_TYPES.iter().any(|t| ty == t) {
+                        return Err(meta.error("Expected a primitive integer type"));
+                    }
+                    if int_types.contains(ty) {
+                        return Err(meta.error(format_args!("`{ty}` already set")));
+                    }
+                    int_types.push(ty.clone());
+                    Ok(())
+                })?;
+            } else if id == "reduce" {
+                set_flag_checked(reduce, &id)?;
+            } else if id == "mask" {
+                set_flag_checked(mask, &id)?;
+            } else if id == "fieldset" {
+                set_flag_checked(fieldset, &id)?;
+                parse_flag_opts(input, |meta| {
+                    if meta.path.is_ident("name") {
+                        let span = meta.path.span();
+                        set_opt_checked(&mut names.fieldset, meta.value()?.parse()?, "name", span)
+                    } else {
+                        Err(meta.error("Expected `name`"))
+                    }
+                })?;
+            } else if id == "patch" {
+                set_flag_checked(patch, &id)?;
+            } else if id == "async" {
+                set_flag_checked(r#async, &id)?;
+            } else if id == "soa" {
+                set_flag_checked(soa, &id)?;
+                parse_flag_opts(input, |meta| {
+                    if meta.path.is_ident("name") {
+                        let span = meta.path.span();
+                        set_opt_checked(&mut names.soa, meta.value()?.parse()?, "name", span)
+                    } else {
+                        Err(meta.error("Expected `name`"))
+                    }
+                })?;
+            } else if id == "transpose" {
+                set_flag_checked(transpose, &id)?;
+            } else if id == "field_enum" {
+                set_flag_checked(field_enum, &id)?;
+                parse_flag_opts(input, |meta| {
+                    if meta.path.is_ident("name") {
+                        let span = meta.path.span();
+                        set_opt_checked(&mut names.field_enum, meta.value()?.parse()?, "name", span)
+                    } else {
+                        Err(meta.error("Expected `name`"))
+                    }
+                })?;
+            } else if id == "consts" {
+                if !input.peek(Paren) {
+                    return Err(syn::Error::new(id.span(), "Expected `consts(...)`"));
+                }
+                parse_flag_opts(input, |meta| {
+                    let span = meta.path.span();
+                    if meta.path.is_ident("prefix") {
+                        set_opt_checked(
+                            &mut names.consts_prefix,
+                            meta.value()?.parse()?,
+                            "prefix",
+                            span,
+                        )
+                    } else if meta.path.is_ident("free") {
+                        set_opt_checked(&mut consts, ConstsMode::Free, "consts mode", span)
+                    } else if meta.path.is_ident("assoc") {
+                        set_opt_checked(&mut consts, ConstsMode::Assoc, "consts mode", span)
+                    } else if meta.path.is_ident("mod") {
+                        if meta.input.peek(Token![=]) {
+                            set_opt_checked(
+                                &mut names.consts_mod,
+                                meta.value()?.parse()?,
+                                "mod",
+                                span,
+                            )?;
+                        }
+                        set_opt_checked(&mut consts, ConstsMode::Mod, "consts mode", span)
+                    } else {
+                        Err(meta.error("Expected one of [`prefix`, `free`, `assoc`, `mod`]"))
+                    }
+                })?;
+                if let (Some(prefix), Some(ConstsMode::Assoc | ConstsMode::Mod)) =
+                    (&names.consts_prefix, consts)
+                {
+                    return Err(syn::Error::new(
+                        prefix.span(),
+                        "`prefix` only applies to free consts",
+                    ));
+                }
+            } else if let Some(pattern) = [
+                ("getter", &mut names.getter),
+                ("getter_mut", &mut names.getter_mut),
+                ("setter", &mut names.setter),
+                ("with", &mut names.with),
+                ("const_with", &mut names.const_with),
+            ]
+            .into_iter()
+            .find_map(|(name, pattern)| (id == name).then_some(pattern))
+            {
+                input.parse::<Token![=]>()?;
+                let lit: LitStr = input.parse()?;
+                let span = id.span();
+                set_opt_checked(pattern, lit, &id.to_string(), span)?;
+            } else {
+                return Err(syn::Error::new(
+                    id.span(),
+                    "Expected one of [`all`, `builder`, `destr`, `trymap`, `zip`, `macro_rules`, \
+                    `ctor`, `field_enum`, `ops`, `int_ops`, `reduce`, \
+                    `mask`, `fieldset`, `patch`, `async`, `soa`, `transpose`, `consts`, `getter`, `getter_mut`, `setter`, `with`, `const_with`]",
+                ));
+            }
+
+            // optional comma separators
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        if all.is_some() {
+            // only allowed with options since `all` already enables them
+            if let Some(id) = bare_all_args.first() {
+                return Err(syn::Error::new(
+                    id.span(),
+                    format!("`{id}` is already enabled by `all`, only specify it to pass options e.g. `{id}(...)`"),
+                ));
+            }
+            *builder = true;
+            *destr = true;
+            *trymap = true;
+            *zip = true;
+        }
+
+        let array_field_vis = if input.is_empty() {
+            Visibility::Inherited
+        } else {
+            input.parse()?
+        };
+        Ok(Self {
+            array_field_vis,
+            flags,
+            consts: consts.unwrap_or_default(),
+            names,
+            macro_path,
+            int_types,
+            trymap_opts,
+            zip_arity: zip_arity.unwrap_or(2),
+        })
+    }
+}
@@ -2,81 +2,117 @@
 
 use std::iter::once;
 
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, ToTokens};
 use syn::{
+    parse_quote,
     punctuated::Punctuated,
     token::{Colon, Const},
     AngleBracketedGenericArguments, ConstParam, Expr, ExprLit, GenericArgument, GenericParam,
     Generics, Ident, Lit, LitBool, Token, Type, TypeParam, TypePath, Visibility,
 };
 
-use crate::{
-    idents::{array_len_ident, field_idx_ident, with_ident},
-    utils::path_from_ident,
-    GenericArrayStructParams,
-};
+use crate::{utils::path_from_ident, GenericArrayStructParams};
+
+/// Methods and associated consts generated on the builder struct, excluding the per-field setters
+pub(crate) const METHODS: &[&str] = &["start", "build", "_UNINIT"];
 
 /// Outputs the token stream to append
 pub(crate) fn impl_builder(
     params: &GenericArrayStructParams,
     struct_vis: &Visibility,
 ) -> proc_macro2::TokenStream {
-    let n_fields = params.fields_named().named.iter().count();
+    let n_fields = params.fields().len();
     let generic_id = params.generic_ident();
     let struct_id = params.struct_ident();
-    let builder_id = format_ident!("{}Builder", struct_id);
+    let builder_id = params.builder_ident();
 
     let mut res = quote! {};
     let mut drop_impl = quote! {};
-    params
-        .fields_named()
-        .named
-        .iter()
-        .enumerate()
-        .for_each(|(i, field)| {
-            let params = generic_params(generic_id, n_fields, Some(i));
-            let [gen_args_false, gen_args_true] =
-                [false, true].map(|hole| generic_args(generic_id, n_fields, Some((i, hole))));
-            // unwrap-safety: named field checked above by params.fields_named()
-            let field_id = field.ident.as_ref().unwrap();
-            let field_vis = &field.vis;
-            let idx_id = field_idx_ident(struct_id, field_id);
-            let cgid_i = cgid(i);
-            let with_id = with_ident(field_id);
-
-            res.extend(quote! {
-                impl #params #builder_id #gen_args_false {
-                    #[inline]
-                    #field_vis const fn #with_id(
-                        mut self,
-                        val: #generic_id,
-                    ) -> #builder_id #gen_args_true {
-                        // use raw array indices instead of mut references to preserve const
-                        self.0[#idx_id] = core::mem::MaybeUninit::new(val);
-                        unsafe {
-                            core::mem::transmute_copy::<_, _>(
-                                &core::mem::ManuallyDrop::new(self)
-                            )
-                        }
-                    }
-                }
-            });
-            drop_impl.extend(quote! {
-                if #cgid_i {
+    let mut clone_impl = quote! {};
+    let mut eq_impl = quote! {};
+    let mut debug_impl = quote! {};
+    params.fields().iter().enumerate().for_each(|(i, field)| {
+        let gen_params = generic_params(generic_id, n_fields, Some(i));
+        let [gen_args_false, gen_args_true] =
+            [false, true].map(|hole| generic_args(generic_id, n_fields, Some((i, hole))));
+        let field_id = field.ident();
+        let field_vis = field.vis();
+        let idx_id = params.field_idx_expr(field);
+        let cgid_i = cgid(i);
+        let with_id = field.builder_with();
+
+        res.extend(quote! {
+            impl #gen_params #builder_id #gen_args_false {
+                #[inline]
+                #field_vis const fn #with_id(
+                    mut self,
+                    val: #generic_id,
+                ) -> #builder_id #gen_args_true {
+                    // use raw array indices instead of mut references to preserve const
+                    self.0[#idx_id] = core::mem::MaybeUninit::new(val);
                     unsafe {
-                        self.0[#idx_id].assume_init_drop();
+                        core::mem::transmute_copy::<_, _>(
+                            &core::mem::ManuallyDrop::new(self)
+                        )
                     }
                 }
-            });
+            }
+        });
+        drop_impl.extend(quote! {
+            if #cgid_i {
+                unsafe {
+                    self.0[#idx_id].assume_init_drop();
+                }
+            }
+        });
+        clone_impl.extend(quote! {
+            if #cgid_i {
+                res[#idx_id].write(unsafe { self.0[#idx_id].assume_init_ref() }.clone());
+            }
+        });
+        eq_impl.extend(quote! {
+            && (
+                !#cgid_i
+                    || unsafe {
+                        self.0[#idx_id].assume_init_ref() == other.0[#idx_id].assume_init_ref()
+                    }
+            )
         });
+        let field_name = field_id.to_string();
+        debug_impl.extend(quote! {
+            if #cgid_i {
+                d.field(#field_name, unsafe { self.0[#idx_id].assume_init_ref() });
+            } else {
+                d.field(#field_name, &format_args!("<unset>"));
+            }
+        });
+    });
 
-    let new_builder_id = format_ident!("New{builder_id}");
+    let new_builder_id = params.new_builder_ident();
     let [all_false_gen_args, all_true_gen_args] =
         [false, true].map(|b| generic_args_fill(generic_id, n_fields, b));
     let just_param = ident_to_gen_param(generic_id.clone());
-    let len_id = array_len_ident(struct_id);
+    let len_id = params.len_expr();
     let all_gen_params = generic_params(generic_id, n_fields, None);
     let all_gen_args = generic_args(generic_id, n_fields, None);
+    let builder_name = builder_id.to_string();
+
+    // extra fields are passed to `build()`, in declaration order
+    let build_generics = build_generics(params);
+    let (build_gen_params, _, build_where_clause) = build_generics.split_for_impl();
+    let (_, struct_gen_args, _) = params.generics().split_for_impl();
+    let extra_args = params.extras().iter().filter(|f| !f.is_phantom()).map(|f| {
+        let ident = f.ident();
+        let ty = f.ty();
+        quote! { #ident: #ty }
+    });
+    let extra_vals = params.extras().iter().map(|f| {
+        if f.is_phantom() {
+            quote! { core::marker::PhantomData }
+        } else {
+            f.ident().into_token_stream()
+        }
+    });
 
     res.extend(quote! {
         #[repr(transparent)]
@@ -95,14 +131,18 @@ pub(crate) fn impl_builder(
 
         impl<#just_param> #builder_id #all_true_gen_args {
             #[inline]
-            #struct_vis const fn build(self) -> #struct_id<#generic_id> {
-                unsafe {
-                    #struct_id(
+            #struct_vis const fn build #build_gen_params (
+                self,
+                #(#extra_args),*
+            ) -> #struct_id #struct_gen_args #build_where_clause {
+                #struct_id(
+                    unsafe {
                         core::mem::transmute_copy::<_, _>(
                             &core::mem::ManuallyDrop::new(self)
                         )
-                    )
-                }
+                    },
+                    #(#extra_vals),*
+                )
             }
         }
 
@@ -113,10 +153,33 @@ pub(crate) fn impl_builder(
             }
         }
 
-        impl #all_gen_params Clone for #builder_id #all_gen_args where #generic_id: Copy {
+        impl #all_gen_params Clone for #builder_id #all_gen_args where #generic_id: Clone {
             #[inline]
             fn clone(&self) -> Self {
-                Self(self.0)
+                // if a clone() panics midway, the already cloned fields are leaked, not dropped
+                let mut res: [core::mem::MaybeUninit<#generic_id>; #len_id] =
+                    core::array::from_fn(|_| core::mem::MaybeUninit::uninit());
+                #clone_impl
+                Self(res)
+            }
+        }
+
+        impl #all_gen_params PartialEq for #builder_id #all_gen_args where #generic_id: PartialEq {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                true #eq_impl
+            }
+        }
+
+        impl #all_gen_params Eq for #builder_id #all_gen_args where #generic_id: Eq {}
+
+        impl #all_gen_params core::fmt::Debug for #builder_id #all_gen_args
+            where #generic_id: core::fmt::Debug
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut d = f.debug_struct(#builder_name);
+                #debug_impl
+                d.finish()
             }
         }
     });
@@ -126,6 +189,35 @@ pub(crate) fn impl_builder(
     res
 }
 
+/// Generics of the `build()` method:
+/// all generics of the struct except the array element type param,
+/// whose bounds are moved to the where clause since it is a param of the impl block.
+fn build_generics(params: &GenericArrayStructParams) -> Generics {
+    let generic_id = params.generic_ident();
+    let mut res = params.generics().clone();
+    let mut elem_bounds = None;
+    res.params = core::mem::take(&mut res.params)
+        .into_iter()
+        .filter(|p| match p {
+            GenericParam::Type(tp) if tp.ident == *generic_id => {
+                elem_bounds = Some(tp.bounds.clone());
+                false
+            }
+            _ => true,
+        })
+        .collect();
+    if let Some(bounds) = elem_bounds.filter(|b| !b.is_empty()) {
+        res.make_where_clause()
+            .predicates
+            .push(parse_quote! { #generic_id: #bounds });
+    }
+    if res.params.is_empty() {
+        res.lt_token = None;
+        res.gt_token = None;
+    }
+    res
+}
+
 /// e.g.
 ///
 /// - `generic_args(T, 3, Some((1, true)))` generates:
@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+
+use proc_macro2::Span;
+use syn::Ident;
+
+use crate::{
+    args::{ConstsMode, Flags},
+    builder, ctor, destr, field_enum, fieldset,
+    idents::{assoc_field_idx_ident, field_variant_ident, mod_field_idx_ident},
+    int_ops, join, macro_rules, mask, patch, reduce, trymap, zip, GenericArrayStructParams,
+};
+
+/// Checks that the names of generated items do not collide with each other,
+/// reporting an error at the offending field's span if they do.
+///
+/// Items that are not generated for a specific field are claimed first
+/// so that collisions with them are reported at the field's span.
+pub(crate) fn check_collisions(params: &GenericArrayStructParams) -> syn::Result<()> {
+    let Flags {
+        builder,
+        destr,
+        trymap,
+        zip,
+        macro_rules,
+        ctor,
+        int_ops,
+        reduce,
+        mask,
+        fieldset,
+        patch,
+        r#async,
+        soa,
+        // claimed through `params.emit_field_enum()`
+        field_enum: _,
+        // only generate trait impls, which cannot collide with inherent items.
+        // Inherent items may however shadow trait methods of the same name,
+        // e.g. `ctor`'s `from_fn` shadows `GenericArrayStruct::from_fn`
+        ops: _,
+        transpose: _,
+    } = params.args().flags;
+    let struct_id = params.struct_ident();
+
+    let mut types = Namespace::default();
+    let mut methods = Namespace::default();
+    let mut builder_methods = Namespace::default();
+    let mut consts = Namespace::default();
+    let mut variants = Namespace::default();
+
+    types.claim(struct_id.to_string(), struct_id.span(), || {
+        format!("struct `{struct_id}`")
+    })?;
+    if builder {
+        for id in [params.builder_ident(), params.new_builder_ident()] {
+            types.claim(id.to_string(), id.span(), || {
+                format!("`builder` arg type `{id}`")
+            })?;
+        }
+        for m in builder::METHODS {
+            builder_methods.claim_static(m, || format!("`builder` arg item `{m}`"))?;
+        }
+    }
+    if destr {
+        let id = params.destr_ident();
+        types.claim(id.to_string(), id.span(), || {
+            format!("`destr` arg type `{id}`")
+        })?;
+    }
+
+    if soa {
+        let id = params.soa_ident();
+        types.claim(id.to_string(), id.span(), || {
+            format!("`soa` arg type `{id}`")
+        })?;
+    }
+
+    let emit_field_enum = params.emit_field_enum();
+    if emit_field_enum {
+        let id = params.field_enum_ident();
+        types.claim(id.to_string(), id.span(), || format!("field enum `{id}`"))?;
+        for m in field_enum::METHODS {
+            variants.claim_static(m, || format!("field enum item `{m}`"))?;
+        }
+    }
+
+    if fieldset {
+        for id in [params.fieldset_ident(), params.fieldset_iter_ident()] {
+            types.claim(id.to_string(), id.span(), || {
+                format!("`fieldset` arg type `{id}`")
+            })?;
+        }
+    }
+
+    methods.claim_static("LEN", || "associated const `LEN`".to_owned())?;
+    for (enabled, arg, arg_methods) in [
+        (destr, "destr", destr::METHODS),
+        (trymap, "trymap", trymap::METHODS),
+        (zip, "zip", zip::METHODS),
+        (macro_rules, "macro_rules", macro_rules::METHODS),
+        (ctor, "ctor", ctor::METHODS),
+        (reduce, "reduce", reduce::METHODS),
+        (mask, "mask", mask::METHODS),
+        (fieldset, "fieldset", fieldset::METHODS),
+        (patch, "patch", patch::METHODS),
+        (r#async, "async", join::METHODS),
+    ] {
+        if enabled {
+            for m in arg_methods {
+                methods.claim_static(m, || format!("`{arg}` arg method `{m}()`"))?;
+            }
+        }
+    }
+
+    if destr && params.emit_const_destr() {
+        for m in destr::CONST_METHODS {
+            methods.claim_static(m, || format!("`destr` arg method `{m}()`"))?;
+        }
+    }
+
+    if trymap && params.args().trymap_opts.alloc {
+        for m in trymap::ALLOC_METHODS {
+            methods.claim_static(m, || format!("`trymap(alloc)` arg method `{m}()`"))?;
+        }
+    }
+
+    if trymap && params.args().trymap_opts.named {
+        for m in trymap::NAMED_METHODS {
+            methods.claim_static(m, || format!("`trymap(named)` arg method `{m}()`"))?;
+        }
+    }
+
+    if trymap && params.args().trymap_opts.generic {
+        for m in trymap::GENERIC_METHODS {
+            methods.claim_static(m, || format!("`trymap(generic)` arg method `{m}()`"))?;
+        }
+    }
+
+    if zip {
+        for m in zip::nary_methods(params.args().zip_arity) {
+            methods.claim(m.clone(), Span::call_site(), || {
+                format!("`zip` arg method `{m}()`")
+            })?;
+        }
+    }
+
+    if int_ops {
+        for m in int_ops::methods() {
+            methods.claim(m.clone(), Span::call_site(), || {
+                format!("`int_ops` arg method `{m}()`")
+            })?;
+        }
+    }
+
+    match params.args().consts {
+        ConstsMode::Free => {
+            let id = params.len_ident();
+            consts.claim_static(&id.to_string(), || format!("const `{id}`"))?;
+        }
+        ConstsMode::Mod => {
+            let id = params.consts_mod_ident();
+            types.claim(id.to_string(), id.span(), || format!("module `{id}`"))?;
+            consts.claim_static("LEN", || format!("const `{id}::LEN`"))?;
+        }
+        ConstsMode::Assoc => {}
+    }
+
+    for field in params.fields() {
+        let field_id = field.ident();
+        let span = field_id.span();
+        let desc = |kind: &str, id: &Ident| format!("{kind} `{id}` of field `{field_id}`");
+
+        for (kind, id) in [
+            ("getter", field.getter()),
+            ("mutable getter", field.getter_mut()),
+            ("setter", field.setter()),
+            ("with method", field.with()),
+            ("const with method", field.const_with()),
+        ] {
+            if let Some(id) = id {
+                methods.claim(id.to_string(), span, || desc(kind, id))?;
+            }
+        }
+        let assoc_idx_id = assoc_field_idx_ident(field_id);
+        methods.claim(assoc_idx_id.to_string(), span, || {
+            desc("associated const", &assoc_idx_id)
+        })?;
+
+        if emit_field_enum {
+            let id = field_variant_ident(field_id);
+            variants.claim(id.to_string(), span, || desc("field enum variant", &id))?;
+        }
+
+        if builder {
+            let id = field.builder_with();
+            builder_methods.claim(id.to_string(), span, || desc("builder method", id))?;
+        }
+
+        let const_id = match params.args().consts {
+            ConstsMode::Free => Some(params.field_idx_ident(field)),
+            ConstsMode::Mod => Some(mod_field_idx_ident(field_id)),
+            ConstsMode::Assoc => None,
+        };
+        if let Some(id) = const_id {
+            consts.claim(id.to_string(), span, || desc("index const", &id))?;
+        }
+    }
+
+    for field in params.extras() {
+        let field_methods = match field.methods() {
+            Some(m) => m,
+            None => continue,
+        };
+        let field_id = field.ident();
+        let span = field_id.span();
+        let desc = |kind: &str, id: &Ident| format!("{kind} `{id}` of field `{field_id}`");
+
+        for (kind, id) in [
+            ("getter", field_methods.getter()),
+            ("mutable getter", field_methods.getter_mut()),
+            ("setter", field_methods.setter()),
+            ("with method", field_methods.with()),
+        ] {
+            if let Some(id) = id {
+                methods.claim(id.to_string(), span, || desc(kind, id))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Names claimed so far in a single namespace, mapped to their descriptions
+#[derive(Default)]
+struct Namespace(HashMap<String, String>);
+
+impl Namespace {
+    fn claim(
+        &mut self,
+        name: String,
+        span: Span,
+        desc: impl FnOnce() -> String,
+    ) -> syn::Result<()> {
+        let desc = desc();
+        match self.0.get(&name) {
+            Some(existing) => Err(syn::Error::new(
+                span,
+                format!("generated {desc} collides with {existing}"),
+            )),
+            None => {
+                self.0.insert(name, desc);
+                Ok(())
+            }
+        }
+    }
+
+    fn claim_static(&mut self, name: &str, desc: impl FnOnce() -> String) -> syn::Result<()> {
+        self.claim(name.to_owned(), Span::call_site(), desc)
+    }
+}
@@ -0,0 +1,57 @@
+use quote::quote;
+use syn::Visibility;
+
+use crate::{idents::field_variant_ident, GenericArrayStructParams};
+
+/// Methods generated on the struct
+pub(crate) const METHODS: &[&str] = &["new", "splat", "from_fn", "try_from_fn"];
+
+/// Outputs the token stream to append
+pub(crate) fn impl_ctor(
+    params: &GenericArrayStructParams,
+    struct_vis: &Visibility,
+) -> proc_macro2::TokenStream {
+    let struct_id = params.struct_ident();
+    let enum_id = params.field_enum_ident();
+    let len = params.len_expr();
+
+    // params in declaration order, array elements in array index order
+    let new_params = params.fields().iter().map(|f| f.ident());
+    let new_elems = params.fields_by_idx().into_iter().map(|f| f.ident());
+    let variants: Vec<_> = params
+        .fields_by_idx()
+        .into_iter()
+        .map(|f| field_variant_ident(f.ident()))
+        .collect();
+
+    quote! {
+        impl<T> #struct_id <T> {
+            #[inline]
+            #struct_vis const fn new(#(#new_params: T),*) -> Self {
+                Self([#(#new_elems),*])
+            }
+
+            /// `f` is called on each field in array index order
+            #[inline]
+            #struct_vis fn from_fn<F>(mut f: F) -> Self where F: FnMut(#enum_id) -> T {
+                Self([#(f(#enum_id::#variants)),*])
+            }
+
+            /// `f` is called on each field in array index order,
+            /// returning the first error encountered
+            #[inline]
+            #struct_vis fn try_from_fn<E, F>(
+                mut f: F,
+            ) -> Result<Self, E> where F: FnMut(#enum_id) -> Result<T, E> {
+                Ok(Self([#(f(#enum_id::#variants)?),*]))
+            }
+        }
+
+        impl<T: Copy> #struct_id <T> {
+            #[inline]
+            #struct_vis const fn splat(val: T) -> Self {
+                Self([val; #len])
+            }
+        }
+    }
+}
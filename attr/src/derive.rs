@@ -0,0 +1,139 @@
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Attribute, Data, DeriveInput, Fields, FieldsNamed, Ident, Token, Type, TypeArray, Visibility,
+};
+
+use crate::{
+    args::AttrArgs, collisions::check_collisions, expand, fields::FIELD_ATTR_NAME,
+    GenericArrayStructParams,
+};
+
+/// Name of the struct-level helper attribute listing the names of the array's fields
+/// e.g. `#[fields(r, g, b)]`
+pub(crate) const FIELDS_ATTR_NAME: &str = "fields";
+
+/// A field in `#[fields(...)]` e.g. `#[gas(skip_mut)] pub r`
+struct FieldDecl {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+}
+
+impl Parse for FieldDecl {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            attrs: input.call(Attribute::parse_outer)?,
+            vis: input.parse()?,
+            ident: input.parse()?,
+        })
+    }
+}
+
+/// Outputs the token stream for `#[derive(ArrayFields)]`.
+///
+/// This converts the tuple struct into the equivalent named-field struct
+/// that `#[generic_array_struct]` would have been applied to,
+/// and generates the same items without outputting the struct.
+pub(crate) fn derive_array_fields(mut input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let TypeArray { elem, len, .. } = array_field(&input)?.clone();
+
+    let mut args: Option<AttrArgs> = None;
+    let mut decls: Option<Punctuated<FieldDecl, Token![,]>> = None;
+    for attr in input.attrs.iter() {
+        if attr.path().is_ident(FIELD_ATTR_NAME) {
+            if args.is_some() {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    format!("duplicate `#[{FIELD_ATTR_NAME}(...)]` attribute"),
+                ));
+            }
+            let parsed: AttrArgs = attr.parse_args()?;
+            if !matches!(parsed.array_field_vis, Visibility::Inherited) {
+                return Err(syn::Error::new(
+                    parsed.array_field_vis.span(),
+                    "visibility of the array field is taken from the struct definition",
+                ));
+            }
+            args = Some(parsed);
+        } else if attr.path().is_ident(FIELDS_ATTR_NAME) {
+            if decls.is_some() {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    format!("duplicate `#[{FIELDS_ATTR_NAME}(...)]` attribute"),
+                ));
+            }
+            decls = Some(attr.parse_args_with(Punctuated::parse_terminated)?);
+        }
+    }
+    let decls = decls.ok_or_else(|| {
+        syn::Error::new(
+            input.ident.span(),
+            format!("`#[{FIELDS_ATTR_NAME}(...)]` attribute listing the field names is required"),
+        )
+    })?;
+    let args = match args {
+        Some(a) => a,
+        None => syn::parse_str::<AttrArgs>("")?,
+    };
+
+    // helper attributes must not be copied onto generated items e.g. the `destr` struct
+    input.attrs.retain(|attr| {
+        !attr.path().is_ident(FIELD_ATTR_NAME) && !attr.path().is_ident(FIELDS_ATTR_NAME)
+    });
+    let fields = decls.iter().map(|FieldDecl { attrs, vis, ident }| {
+        quote! {
+            #(#attrs)*
+            #vis #ident: #elem
+        }
+    });
+    let fields: FieldsNamed = syn::parse_quote! {
+        { #(#fields),* }
+    };
+    // always a struct, checked by array_field() above
+    if let Data::Struct(ds) = &mut input.data {
+        ds.fields = Fields::Named(fields);
+    }
+
+    let params = GenericArrayStructParams::new(input, args)?;
+    if let Some(extra) = params.extras().first() {
+        return Err(syn::Error::new(
+            extra.ident().span(),
+            "extra fields are not supported by `#[derive(ArrayFields)]`",
+        ));
+    }
+    check_collisions(&params)?;
+
+    let mut res = expand(&params);
+    let n_fields = params.fields().len();
+    let struct_id = params.struct_ident();
+    let msg =
+        format!("length of the array of `{struct_id}` must be {n_fields}, the number of fields");
+    res.extend(quote! {
+        const _: () = assert!(#len == #n_fields, #msg);
+    });
+    Ok(res)
+}
+
+/// The single array field of the tuple struct e.g. `[T; 3]` in `struct Rgb<T>(pub [T; 3])`
+fn array_field(input: &DeriveInput) -> syn::Result<&TypeArray> {
+    let err = || {
+        syn::Error::new(
+            input.ident.span(),
+            "`#[derive(ArrayFields)]` only works with tuple structs with a single array field",
+        )
+    };
+    let fields = match &input.data {
+        Data::Struct(ds) => match &ds.fields {
+            Fields::Unnamed(f) => &f.unnamed,
+            _ => return Err(err()),
+        },
+        _ => return Err(err()),
+    };
+    match (fields.first().map(|f| &f.ty), fields.len()) {
+        (Some(Type::Array(arr)), 1) => Ok(arr),
+        _ => Err(err()),
+    }
+}
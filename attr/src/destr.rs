@@ -1,70 +1,92 @@
-use quote::{format_ident, quote};
-use syn::{Attribute, Ident, Visibility};
+use quote::quote;
+use syn::{parse_quote, Attribute, Ident, Visibility};
 
 use crate::GenericArrayStructParams;
 
+/// Methods generated on the struct
+pub(crate) const METHODS: &[&str] = &["from_destr", "into_destr"];
+
+/// Methods generated on the struct if [`GenericArrayStructParams::emit_const_destr`]
+pub(crate) const CONST_METHODS: &[&str] = &["const_from_destr", "const_into_destr"];
+
 /// Outputs the token stream to append
 pub(crate) fn impl_destr(
     params: &GenericArrayStructParams,
     struct_vis: &Visibility,
 ) -> proc_macro2::TokenStream {
-    let field_idents = params
-        .fields_named()
-        .named
-        .iter()
-        .map(|f| f.ident.as_ref().expect("all fields should be named"));
-    let field_seq = quote! {
-        #(#field_idents),*
+    let struct_id = params.struct_ident();
+    let generic = params.generic_ident();
+    let destr_id = params.destr_ident();
+    let og_fields = &params.og_fields().named;
+
+    // in array index order, which can differ from declaration order,
+    // followed by the extra fields in declaration order
+    let array_idents = params.fields_by_idx().into_iter().map(|f| f.ident());
+    let extra_idents = params.extras().iter().map(|f| f.ident());
+    let tuple_pat = quote! {
+        [#(#array_idents),*], #(#extra_idents),*
+    };
+    let all_idents = og_fields.iter().map(|f| f.ident.as_ref());
+    let struct_pat = quote! {
+        #(#all_idents),*
     };
 
-    let struct_id = params.struct_ident();
-    let generic_ident = params.generic_ident();
-    let destr_id = format_ident!("{struct_id}Destr");
-    let og_fields = params.data_struct().fields.iter();
     let attrs = params.attrs().iter().filter(|a| is_attr_compat(a));
+    let generics = params.generics();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let copy_generics = params.generics_with_predicate(parse_quote! { #generic: Copy });
+    let (copy_impl_generics, _, copy_where_clause) = copy_generics.split_for_impl();
 
-    quote! {
-        #(#attrs)*
-        #struct_vis struct #destr_id <#generic_ident> {
-            #(#og_fields),*
-        }
+    let const_impl = if params.emit_const_destr() {
+        quote! {
+            impl #copy_impl_generics #struct_id #ty_generics #copy_where_clause {
+                #[inline]
+                pub const fn const_from_destr(#destr_id { #struct_pat }: #destr_id #ty_generics) -> Self {
+                    Self(#tuple_pat)
+                }
 
-        impl<T> #struct_id <T> {
-            #[inline]
-            pub fn from_destr(#destr_id { #field_seq }: #destr_id <T>) -> Self {
-                Self([ #field_seq ])
+                #[inline]
+                pub const fn const_into_destr(self) -> #destr_id #ty_generics {
+                    let Self(#tuple_pat) = self;
+                    #destr_id { #struct_pat }
+                }
             }
+        }
+    } else {
+        quote! {}
+    };
 
-            #[inline]
-            pub fn into_destr(self) -> #destr_id <T> {
-                let Self([ #field_seq ]) = self;
-                #destr_id { #field_seq }
-            }
+    quote! {
+        #(#attrs)*
+        #struct_vis struct #destr_id #generics #where_clause {
+            #og_fields
         }
 
-        impl<T: Copy> #struct_id <T> {
+        impl #impl_generics #struct_id #ty_generics #where_clause {
             #[inline]
-            pub const fn const_from_destr(#destr_id { #field_seq }: #destr_id <T>) -> Self {
-                Self([ #field_seq ])
+            pub fn from_destr(#destr_id { #struct_pat }: #destr_id #ty_generics) -> Self {
+                Self(#tuple_pat)
             }
 
             #[inline]
-            pub const fn const_into_destr(self) -> #destr_id <T> {
-                let Self([ #field_seq ]) = self;
-                #destr_id { #field_seq }
+            pub fn into_destr(self) -> #destr_id #ty_generics {
+                let Self(#tuple_pat) = self;
+                #destr_id { #struct_pat }
             }
         }
 
-        impl<T> From<#destr_id <T>> for #struct_id <T> {
+        #const_impl
+
+        impl #impl_generics From<#destr_id #ty_generics> for #struct_id #ty_generics #where_clause {
             #[inline]
-            fn from(d: #destr_id <T>) -> Self {
+            fn from(d: #destr_id #ty_generics) -> Self {
                 Self::from_destr(d)
             }
         }
 
-        impl<T> From<#struct_id <T>> for #destr_id <T> {
+        impl #impl_generics From<#struct_id #ty_generics> for #destr_id #ty_generics #where_clause {
             #[inline]
-            fn from(d: #struct_id <T>) -> Self {
+            fn from(d: #struct_id #ty_generics) -> Self {
                 d.into_destr()
             }
         }
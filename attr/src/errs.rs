@@ -1,10 +1,10 @@
 use super::MACRO_NAME;
 
 const REQ_SINGLE_GENERIC_TYPE_PARAM_ERRMSG: &str =
-    "only works with structs with a single generic type param";
+    "only works with structs with a generic type param for the array elements";
 
 const REQ_ALL_FIELDS_SAME_GENERIC_TYPE_ERRMSG: &str =
-    "requires all fields to have the same generic type";
+    "requires all fields not marked `#[gas(extra)]` to have the same generic type";
 
 const ONLY_WORKS_WITH_STRUCTS_ERRMSG: &str = "only works with structs";
 
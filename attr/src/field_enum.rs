@@ -0,0 +1,82 @@
+use quote::quote;
+use syn::{ext::IdentExt, Visibility};
+
+use crate::{idents::field_variant_ident, GenericArrayStructParams};
+
+/// Methods and associated consts generated on the field enum
+pub(crate) const METHODS: &[&str] = &["ALL", "idx", "name", "from_idx"];
+
+/// Outputs the token stream to append
+pub(crate) fn impl_field_enum(
+    params: &GenericArrayStructParams,
+    struct_vis: &Visibility,
+) -> proc_macro2::TokenStream {
+    let struct_id = params.struct_ident();
+    let enum_id = params.field_enum_ident();
+    let len = params.len_expr();
+
+    // in array index order so that the derived Ord follows it
+    let fields = params.fields_by_idx();
+    let variants: Vec<_> = fields
+        .iter()
+        .map(|f| field_variant_ident(f.ident()))
+        .collect();
+    let idx_arms = fields.iter().zip(&variants).map(|(f, v)| {
+        let idx = params.field_idx_expr(f);
+        quote! { Self::#v => #idx }
+    });
+    let name_arms = fields.iter().zip(&variants).map(|(f, v)| {
+        let name = f.ident().unraw().to_string();
+        quote! { Self::#v => #name }
+    });
+    let from_idx_arms = variants.iter().enumerate().map(|(i, v)| {
+        quote! { #i => Some(Self::#v) }
+    });
+
+    let doc = format!("The fields of [`{struct_id}`], in array index order");
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #struct_vis enum #enum_id {
+            #(#variants),*
+        }
+
+        impl #enum_id {
+            /// All fields, in array index order
+            #struct_vis const ALL: [Self; #len] = [#(Self::#variants),*];
+
+            /// Index of the field in the array
+            #[inline]
+            #struct_vis const fn idx(self) -> usize {
+                match self {
+                    #(#idx_arms),*
+                }
+            }
+
+            /// Name of the field as declared
+            #[inline]
+            #struct_vis const fn name(self) -> &'static str {
+                match self {
+                    #(#name_arms),*
+                }
+            }
+
+            /// Returns `None` if `idx` is out of bounds
+            #[inline]
+            #struct_vis const fn from_idx(idx: usize) -> Option<Self> {
+                match idx {
+                    #(#from_idx_arms,)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl core::fmt::Display for #enum_id {
+            #[inline]
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+    }
+}
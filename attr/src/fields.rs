@@ -0,0 +1,482 @@
+use syn::{
+    meta::ParseNestedMeta, Attribute, Field, Ident, Index, LitInt, LitStr, Type, TypePath,
+    Visibility,
+};
+
+use crate::{
+    args::Names,
+    idents::{const_with_ident, ident_mut, pattern_ident, set_ident, with_ident},
+};
+
+/// Name of the field-level helper attribute e.g. `#[gas(skip_mut)]`
+pub(crate) const FIELD_ATTR_NAME: &str = "gas";
+
+/// A field of the original struct with its `#[gas(...)]` attributes parsed and removed
+pub(crate) struct ParsedField {
+    ident: Ident,
+    decl_vis: Visibility,
+    attrs: Vec<Attribute>,
+    ty: Type,
+    opts: FieldOpts,
+}
+
+/// A field of the original struct that is stored in the array
+pub(crate) struct ArrayField {
+    ident: Ident,
+
+    /// Index of the field in the array, assigned by [`assign_indices`]
+    idx: usize,
+
+    /// Set by `#[gas(index = ...)]`
+    explicit_idx: Option<LitInt>,
+
+    /// Attributes of the field as declared in the original struct,
+    /// with `#[gas(...)]` attributes removed
+    attrs: Vec<Attribute>,
+
+    methods: FieldMethods,
+}
+
+/// A field of the original struct that is not stored in the array,
+/// either marked `#[gas(extra)]` or a `PhantomData`
+pub(crate) struct ExtraField {
+    ident: Ident,
+
+    /// Index of the field in the output tuple struct, after the array at `.0`
+    tuple_idx: Index,
+
+    ty: Type,
+
+    /// Visibility of the field as declared in the original struct
+    decl_vis: Visibility,
+
+    /// Attributes of the field as declared in the original struct,
+    /// with `#[gas(...)]` attributes removed
+    attrs: Vec<Attribute>,
+
+    /// `None` for `PhantomData` fields, which do not get any methods
+    methods: Option<FieldMethods>,
+}
+
+/// Names and visibility of the methods generated for a field
+pub(crate) struct FieldMethods {
+    vis: Visibility,
+    getter: Option<Ident>,
+    getter_mut: Option<Ident>,
+    setter: Option<Ident>,
+    with: Ident,
+    const_with: Ident,
+    skip_with: bool,
+}
+
+/// Options set by `#[gas(...)]` field attributes
+#[derive(Default)]
+struct FieldOpts {
+    extra: bool,
+    index: Option<LitInt>,
+    vis: Option<Visibility>,
+    getter: Option<Ident>,
+    getter_mut: Option<Ident>,
+    setter: Option<Ident>,
+    with: Option<Ident>,
+    const_with: Option<Ident>,
+    skip_get: bool,
+    skip_mut: bool,
+    skip_set: bool,
+    skip_with: bool,
+}
+
+impl ParsedField {
+    /// Removes all `#[gas(...)]` attributes from `field` and parses them
+    pub fn new(field: &mut Field) -> syn::Result<Self> {
+        let mut opts = FieldOpts::default();
+        let mut err = Ok(());
+        field.attrs.retain(|attr| {
+            if !attr.path().is_ident(FIELD_ATTR_NAME) {
+                return true;
+            }
+            if err.is_ok() {
+                err = attr.parse_nested_meta(|meta| opts.parse_meta(meta));
+            }
+            false
+        });
+        err?;
+
+        // unwrap-safety: only named fields are parsed
+        let ident = field.ident.clone().unwrap();
+        if opts.extra {
+            if let Some(lit) = &opts.index {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "`index` cannot be used with `extra`",
+                ));
+            }
+        }
+        Ok(Self {
+            ident,
+            decl_vis: field.vis.clone(),
+            attrs: field.attrs.clone(),
+            ty: field.ty.clone(),
+            opts,
+        })
+    }
+
+    #[inline]
+    pub fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    /// Whether this field should not be stored in the array
+    pub fn is_extra(&self) -> bool {
+        self.opts.extra || is_phantom_data(&self.ty)
+    }
+
+    /// Method names not set by field attributes are derived from the struct-level `names`
+    pub fn into_array_field(self, names: &Names) -> syn::Result<ArrayField> {
+        let methods = FieldMethods::new(&self.ident, &self.decl_vis, &self.opts, names)?;
+        let Self {
+            ident, attrs, opts, ..
+        } = self;
+        Ok(ArrayField {
+            ident,
+            idx: 0,
+            explicit_idx: opts.index,
+            attrs,
+            methods,
+        })
+    }
+
+    /// Method names not set by field attributes are derived from the struct-level `names`
+    pub fn into_extra_field(self, tuple_idx: usize, names: &Names) -> syn::Result<ExtraField> {
+        let methods = if is_phantom_data(&self.ty) {
+            None
+        } else {
+            Some(FieldMethods::new(
+                &self.ident,
+                &self.decl_vis,
+                &self.opts,
+                names,
+            )?)
+        };
+        let Self {
+            ident,
+            decl_vis,
+            attrs,
+            ty,
+            ..
+        } = self;
+        Ok(ExtraField {
+            ident,
+            tuple_idx: Index::from(tuple_idx),
+            ty,
+            decl_vis,
+            attrs,
+            methods,
+        })
+    }
+}
+
+impl FieldMethods {
+    fn new(
+        ident: &Ident,
+        decl_vis: &Visibility,
+        opts: &FieldOpts,
+        names: &Names,
+    ) -> syn::Result<Self> {
+        let resolve = |field_override: &Option<Ident>,
+                       pattern: &Option<LitStr>,
+                       default: fn(&Ident) -> Ident| {
+            match (field_override, pattern) {
+                (Some(id), _) => Ok(id.clone()),
+                (None, Some(pattern)) => pattern_ident(pattern, ident),
+                (None, None) => Ok(default(ident)),
+            }
+        };
+        let getter = resolve(&opts.getter, &names.getter, Ident::clone)?;
+        let getter_mut = resolve(&opts.getter_mut, &names.getter_mut, ident_mut)?;
+        let setter = resolve(&opts.setter, &names.setter, set_ident)?;
+        let with = resolve(&opts.with, &names.with, with_ident)?;
+        let const_with = resolve(&opts.const_with, &names.const_with, const_with_ident)?;
+
+        Ok(Self {
+            vis: opts.vis.clone().unwrap_or_else(|| decl_vis.clone()),
+            getter: (!opts.skip_get).then_some(getter),
+            getter_mut: (!opts.skip_mut).then_some(getter_mut),
+            setter: (!opts.skip_set).then_some(setter),
+            with,
+            const_with,
+            skip_with: opts.skip_with,
+        })
+    }
+
+    /// Visibility of all items generated for this field.
+    /// Defaults to the visibility of the original field.
+    #[inline]
+    pub fn vis(&self) -> &Visibility {
+        &self.vis
+    }
+
+    /// e.g. `x`. `None` if skipped.
+    #[inline]
+    pub fn getter(&self) -> Option<&Ident> {
+        self.getter.as_ref()
+    }
+
+    /// e.g. `x_mut`. `None` if skipped.
+    #[inline]
+    pub fn getter_mut(&self) -> Option<&Ident> {
+        self.getter_mut.as_ref()
+    }
+
+    /// e.g. `set_x`. `None` if skipped.
+    #[inline]
+    pub fn setter(&self) -> Option<&Ident> {
+        self.setter.as_ref()
+    }
+
+    /// e.g. `with_x`. `None` if skipped.
+    #[inline]
+    pub fn with(&self) -> Option<&Ident> {
+        (!self.skip_with).then_some(&self.with)
+    }
+
+    /// e.g. `const_with_x`. `None` if skipped.
+    #[inline]
+    pub fn const_with(&self) -> Option<&Ident> {
+        (!self.skip_with).then_some(&self.const_with)
+    }
+}
+
+impl ArrayField {
+    #[inline]
+    pub fn ident(&self) -> &Ident {
+        &self.ident
+    }
+
+    #[inline]
+    pub fn idx(&self) -> usize {
+        self.idx
+    }
+
+    #[inline]
+    pub fn attrs(&self) -> &[Attribute] {
+        &self.attrs
+    }
+
+    /// See [`FieldMethods::vis`]
+    #[inline]
+    pub fn vis(&self) -> &Visibility {
+        self.methods.vis()
+    }
+
+    /// See [`FieldMethods::getter`]
+    #[inline]
+    pub fn getter(&self) -> Option<&Ident> {
+        self.methods.getter()
+    }
+
+    /// See [`FieldMethods::getter_mut`]
+    #[inline]
+    pub fn getter_mut(&self) -> Option<&Ident> {
+        self.methods.getter_mut()
+    }
+
+    /// See [`FieldMethods::setter`]
+    #[inline]
+    pub fn setter(&self) -> Option<&Ident> {
+        self.methods.setter()
+    }
+
+    /// See [`FieldMethods::with`]
+    #[inline]
+    pub fn with(&self) -> Option<&Ident> {
+        self.methods.with()
+    }
+
+    /// See [`FieldMethods::const_with`]
+    #[inline]
+    pub fn const_with(&self) -> Option<&Ident> {
+        self.methods.const_with()
+    }
+
+    /// Name of the builder's setter method for this field, e.g. `with_x`.
+    /// Unaffected by `skip_with` since every field must be settable on the builder.
+    #[inline]
+    pub fn builder_with(&self) -> &Ident {
+        &self.methods.with
+    }
+}
+
+impl ExtraField {
+    #[inline]
+    pub fn ident(&self) -> &Ident {
+        &self.ident
+    }
+
+    #[inline]
+    pub fn tuple_idx(&self) -> &Index {
+        &self.tuple_idx
+    }
+
+    #[inline]
+    pub fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    #[inline]
+    pub fn decl_vis(&self) -> &Visibility {
+        &self.decl_vis
+    }
+
+    #[inline]
+    pub fn attrs(&self) -> &[Attribute] {
+        &self.attrs
+    }
+
+    /// `None` for `PhantomData` fields
+    #[inline]
+    pub fn methods(&self) -> Option<&FieldMethods> {
+        self.methods.as_ref()
+    }
+
+    /// Whether this field is a `PhantomData` that can be constructed without a value
+    #[inline]
+    pub fn is_phantom(&self) -> bool {
+        self.methods.is_none()
+    }
+}
+
+/// Whether `ty` is a path ending in `PhantomData` e.g. `core::marker::PhantomData<T>`
+fn is_phantom_data(ty: &Type) -> bool {
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => path
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "PhantomData"),
+        _ => false,
+    }
+}
+
+/// Assigns array indices to `fields`, which are in declaration order.
+///
+/// Fields with `#[gas(index = ...)]` are placed at that index,
+/// the rest fill the remaining indices in declaration order.
+pub(crate) fn assign_indices(fields: &mut [ArrayField]) -> syn::Result<()> {
+    let n_fields = fields.len();
+    let mut taken = vec![false; n_fields];
+    for field in fields.iter() {
+        let lit = match &field.explicit_idx {
+            Some(l) => l,
+            None => continue,
+        };
+        let i: usize = lit.base10_parse()?;
+        if i >= n_fields {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("index {i} out of range for {n_fields} fields"),
+            ));
+        }
+        if taken[i] {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("index {i} already used by another field"),
+            ));
+        }
+        taken[i] = true;
+    }
+
+    let mut free = (0..n_fields).filter(|i| !taken[*i]);
+    for field in fields.iter_mut() {
+        field.idx = match &field.explicit_idx {
+            Some(lit) => lit.base10_parse()?,
+            // unwrap-safety: number of free indices == number of fields without explicit index
+            None => free.next().unwrap(),
+        };
+    }
+    Ok(())
+}
+
+impl FieldOpts {
+    fn parse_meta(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        let Self {
+            extra,
+            index,
+            vis,
+            getter,
+            getter_mut,
+            setter,
+            with,
+            const_with,
+            skip_get,
+            skip_mut,
+            skip_set,
+            skip_with,
+        } = self;
+
+        let skips = [
+            ("skip_get", skip_get),
+            ("skip_mut", skip_mut),
+            ("skip_set", skip_set),
+            ("skip_with", skip_with),
+        ];
+        for (name, flag) in skips {
+            if meta.path.is_ident(name) {
+                if *flag {
+                    return Err(meta.error(format_args!("`{name}` already set")));
+                }
+                *flag = true;
+                return Ok(());
+            }
+        }
+
+        let names = [
+            ("getter", getter),
+            ("getter_mut", getter_mut),
+            ("setter", setter),
+            ("with", with),
+            ("const_with", const_with),
+        ];
+        for (name, val) in names {
+            if meta.path.is_ident(name) {
+                if val.is_some() {
+                    return Err(meta.error(format_args!("`{name}` already set")));
+                }
+                let s: LitStr = meta.value()?.parse()?;
+                *val = Some(s.parse()?);
+                return Ok(());
+            }
+        }
+
+        if meta.path.is_ident("extra") {
+            if *extra {
+                return Err(meta.error("`extra` already set"));
+            }
+            *extra = true;
+            return Ok(());
+        }
+
+        if meta.path.is_ident("index") {
+            if index.is_some() {
+                return Err(meta.error("`index` already set"));
+            }
+            let lit: LitInt = meta.value()?.parse()?;
+            lit.base10_parse::<usize>()?;
+            *index = Some(lit);
+            return Ok(());
+        }
+
+        if meta.path.is_ident("vis") {
+            if vis.is_some() {
+                return Err(meta.error("`vis` already set"));
+            }
+            let s: LitStr = meta.value()?.parse()?;
+            *vis = Some(s.parse()?);
+            return Ok(());
+        }
+
+        Err(meta.error(
+            "Expected one of [`skip_get`, `skip_mut`, `skip_set`, `skip_with`, \
+            `getter`, `getter_mut`, `setter`, `with`, `const_with`, `vis`, `index`, `extra`]",
+        ))
+    }
+}
@@ -0,0 +1,303 @@
+use proc_macro2::Literal;
+use quote::{format_ident, quote};
+use syn::Visibility;
+
+use crate::{utils::bits_ty, GenericArrayStructParams};
+
+/// Methods generated on the struct
+pub(crate) const METHODS: &[&str] = &["diff", "copy_fields_from"];
+
+/// Outputs the token stream to append
+pub(crate) fn impl_fieldset(
+    params: &GenericArrayStructParams,
+    struct_vis: &Visibility,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let n_fields = params.fields().len();
+    let struct_id = params.struct_ident();
+    let enum_id = params.field_enum_ident();
+    let set_id = params.fieldset_ident();
+    let iter_id = params.fieldset_iter_ident();
+
+    let bits_ty = bits_ty(n_fields, struct_id.span()).ok_or_else(|| {
+        syn::Error::new(
+            struct_id.span(),
+            "`fieldset` arg supports structs with at most 128 fields",
+        )
+    })?;
+    let all_bits = Literal::u128_unsuffixed(match n_fields {
+        128 => u128::MAX,
+        n => (1 << n) - 1,
+    });
+
+    let idxs: Vec<_> = (0..n_fields).collect();
+    let set_doc = format!(
+        "A set of [`{enum_id}`]s, with bit `i` set if the field at array index `i` is in the set"
+    );
+    let iter_doc = format!("Iterator over the fields of a [`{set_id}`], in array index order");
+
+    let set_ops = [
+        ("BitOr", "bitor", "BitOrAssign", "bitor_assign", "union"),
+        (
+            "BitAnd",
+            "bitand",
+            "BitAndAssign",
+            "bitand_assign",
+            "intersection",
+        ),
+        (
+            "BitXor",
+            "bitxor",
+            "BitXorAssign",
+            "bitxor_assign",
+            "symmetric_difference",
+        ),
+        ("Sub", "sub", "SubAssign", "sub_assign", "difference"),
+    ]
+    .map(|(tr, method, tr_assign, method_assign, set_method)| {
+        let [tr, method, tr_assign, method_assign, set_method] =
+            [tr, method, tr_assign, method_assign, set_method]
+                .map(|s| format_ident!("{s}", span = struct_id.span()));
+        quote! {
+            impl core::ops::#tr for #set_id {
+                type Output = Self;
+
+                #[inline]
+                fn #method(self, rhs: Self) -> Self {
+                    self.#set_method(rhs)
+                }
+            }
+
+            impl core::ops::#tr_assign for #set_id {
+                #[inline]
+                fn #method_assign(&mut self, rhs: Self) {
+                    *self = self.#set_method(rhs);
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #[doc = #set_doc]
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+        #struct_vis struct #set_id(#bits_ty);
+
+        impl #set_id {
+            #struct_vis const EMPTY: Self = Self(0);
+
+            #struct_vis const ALL: Self = Self(#all_bits);
+
+            /// Bits at or above the number of fields are ignored
+            #[inline]
+            #struct_vis const fn from_bits(bits: #bits_ty) -> Self {
+                Self(bits & Self::ALL.0)
+            }
+
+            #[inline]
+            #struct_vis const fn bits(self) -> #bits_ty {
+                self.0
+            }
+
+            #[inline]
+            #struct_vis const fn from_field(field: #enum_id) -> Self {
+                Self(1 << field.idx())
+            }
+
+            #[inline]
+            #struct_vis const fn from_fields(fields: &[#enum_id]) -> Self {
+                let mut res = Self::EMPTY;
+                let mut i = 0;
+                while i < fields.len() {
+                    res = res.with(fields[i]);
+                    i += 1;
+                }
+                res
+            }
+
+            #[inline]
+            #struct_vis const fn contains(self, field: #enum_id) -> bool {
+                self.0 & Self::from_field(field).0 != 0
+            }
+
+            #[inline]
+            #struct_vis const fn with(self, field: #enum_id) -> Self {
+                Self(self.0 | Self::from_field(field).0)
+            }
+
+            #[inline]
+            #struct_vis const fn without(self, field: #enum_id) -> Self {
+                Self(self.0 & !Self::from_field(field).0)
+            }
+
+            /// Returns whether `field` was not already in the set
+            #[inline]
+            #struct_vis const fn insert(&mut self, field: #enum_id) -> bool {
+                let res = !self.contains(field);
+                *self = self.with(field);
+                res
+            }
+
+            /// Returns whether `field` was in the set
+            #[inline]
+            #struct_vis const fn remove(&mut self, field: #enum_id) -> bool {
+                let res = self.contains(field);
+                *self = self.without(field);
+                res
+            }
+
+            #[inline]
+            #struct_vis const fn union(self, other: Self) -> Self {
+                Self(self.0 | other.0)
+            }
+
+            #[inline]
+            #struct_vis const fn intersection(self, other: Self) -> Self {
+                Self(self.0 & other.0)
+            }
+
+            /// Fields in `self` but not in `other`
+            #[inline]
+            #struct_vis const fn difference(self, other: Self) -> Self {
+                Self(self.0 & !other.0)
+            }
+
+            #[inline]
+            #struct_vis const fn symmetric_difference(self, other: Self) -> Self {
+                Self(self.0 ^ other.0)
+            }
+
+            /// Fields not in `self`
+            #[inline]
+            #struct_vis const fn complement(self) -> Self {
+                Self(!self.0 & Self::ALL.0)
+            }
+
+            #[inline]
+            #struct_vis const fn is_empty(self) -> bool {
+                self.0 == 0
+            }
+
+            #[inline]
+            #struct_vis const fn is_all(self) -> bool {
+                self.0 == Self::ALL.0
+            }
+
+            #[inline]
+            #struct_vis const fn is_subset(self, other: Self) -> bool {
+                self.0 & !other.0 == 0
+            }
+
+            #[inline]
+            #struct_vis const fn is_superset(self, other: Self) -> bool {
+                other.is_subset(self)
+            }
+
+            /// Number of fields in the set
+            #[inline]
+            #struct_vis const fn len(self) -> usize {
+                self.0.count_ones() as usize
+            }
+
+            #[inline]
+            #struct_vis const fn iter(self) -> #iter_id {
+                #iter_id(self.0)
+            }
+        }
+
+        impl core::ops::Not for #set_id {
+            type Output = Self;
+
+            #[inline]
+            fn not(self) -> Self {
+                self.complement()
+            }
+        }
+
+        #(#set_ops)*
+
+        impl From<#enum_id> for #set_id {
+            #[inline]
+            fn from(field: #enum_id) -> Self {
+                Self::from_field(field)
+            }
+        }
+
+        impl core::iter::FromIterator<#enum_id> for #set_id {
+            #[inline]
+            fn from_iter<I: IntoIterator<Item = #enum_id>>(iter: I) -> Self {
+                let mut res = Self::EMPTY;
+                res.extend(iter);
+                res
+            }
+        }
+
+        impl core::iter::Extend<#enum_id> for #set_id {
+            #[inline]
+            fn extend<I: IntoIterator<Item = #enum_id>>(&mut self, iter: I) {
+                iter.into_iter().for_each(|field| {
+                    self.insert(field);
+                });
+            }
+        }
+
+        impl IntoIterator for #set_id {
+            type Item = #enum_id;
+            type IntoIter = #iter_id;
+
+            #[inline]
+            fn into_iter(self) -> #iter_id {
+                self.iter()
+            }
+        }
+
+        impl core::fmt::Debug for #set_id {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_set().entries(self.iter()).finish()
+            }
+        }
+
+        #[doc = #iter_doc]
+        #[derive(Debug, Clone)]
+        #struct_vis struct #iter_id(#bits_ty);
+
+        impl Iterator for #iter_id {
+            type Item = #enum_id;
+
+            #[inline]
+            fn next(&mut self) -> Option<#enum_id> {
+                if self.0 == 0 {
+                    return None;
+                }
+                let idx = self.0.trailing_zeros() as usize;
+                // clear lowest set bit
+                self.0 &= self.0 - 1;
+                #enum_id::from_idx(idx)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let n = self.0.count_ones() as usize;
+                (n, Some(n))
+            }
+        }
+
+        impl ExactSizeIterator for #iter_id {}
+
+        impl core::iter::FusedIterator for #iter_id {}
+
+        impl<T> #struct_id<T> {
+            /// The fields that differ between `self` and `other`
+            #[inline]
+            #struct_vis fn diff(&self, other: &Self) -> #set_id where T: PartialEq {
+                #set_id(0 #(| (((self.0[#idxs] != other.0[#idxs]) as #bits_ty) << #idxs))*)
+            }
+
+            /// Clones the fields in `fields` from `other` into `self`
+            #[inline]
+            #struct_vis fn copy_fields_from(&mut self, other: &Self, fields: #set_id) where T: Clone {
+                fields
+                    .iter()
+                    .for_each(|field| self.0[field.idx()].clone_from(&other.0[field.idx()]));
+            }
+        }
+    })
+}
@@ -1,6 +1,6 @@
-use heck::ToShoutySnakeCase;
+use heck::{ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 use quote::format_ident;
-use syn::Ident;
+use syn::{ext::IdentExt, Ident, LitStr};
 
 /// e.g. `with_x`
 #[inline]
@@ -20,24 +20,74 @@ pub(crate) fn ident_mut(field_ident: &Ident) -> Ident {
     format_ident!("{field_ident}_mut")
 }
 
+/// e.g. `x_col`
+#[inline]
+pub(crate) fn col_ident(field_ident: &Ident) -> Ident {
+    format_ident!("{field_ident}_col")
+}
+
 /// e.g. `const_with_x`
 #[inline]
 pub(crate) fn const_with_ident(field_ident: &Ident) -> Ident {
     format_ident!("const_with_{field_ident}")
 }
 
+/// Replaces the `{}` in a user-provided pattern with the field ident
+/// e.g. `"put_{}"` -> `put_x`
+pub(crate) fn pattern_ident(pattern: &LitStr, field_ident: &Ident) -> syn::Result<Ident> {
+    let pattern_str = pattern.value();
+    if !pattern_str.contains("{}") {
+        return Err(syn::Error::new(
+            pattern.span(),
+            "pattern must contain a `{}` placeholder for the field name",
+        ));
+    }
+    let ident_str = pattern_str.replace("{}", &field_ident.unraw().to_string());
+    syn::parse_str::<Ident>(&ident_str)
+        .map(|id| Ident::new(&id.to_string(), pattern.span()))
+        .map_err(|_e| {
+            syn::Error::new(
+                pattern.span(),
+                format!("pattern results in invalid identifier `{ident_str}`"),
+            )
+        })
+}
+
+/// e.g. RgbBuilder
+#[inline]
+pub(crate) fn builder_ident(struct_ident: &Ident) -> Ident {
+    format_ident!("{struct_ident}Builder")
+}
+
+/// e.g. NewRgbBuilder
+#[inline]
+pub(crate) fn new_builder_ident(builder_ident: &Ident) -> Ident {
+    format_ident!("New{builder_ident}")
+}
+
+/// e.g. RgbDestr
+#[inline]
+pub(crate) fn destr_ident(struct_ident: &Ident) -> Ident {
+    format_ident!("{struct_ident}Destr")
+}
+
+/// e.g. RGB as in RGB_LEN
+#[inline]
+pub(crate) fn consts_prefix(struct_ident: &Ident) -> String {
+    struct_ident.to_string().to_shouty_snake_case()
+}
+
 /// e.g. RGB_LEN
 #[inline]
-pub(crate) fn array_len_ident(struct_ident: &Ident) -> Ident {
-    format_ident!("{}_LEN", struct_ident.to_string().to_shouty_snake_case())
+pub(crate) fn array_len_ident(consts_prefix: &str) -> Ident {
+    format_ident!("{consts_prefix}_LEN")
 }
 
 /// e.g. RGB_IDX_R
 #[inline]
-pub(crate) fn field_idx_ident(struct_ident: &Ident, field_ident: &Ident) -> Ident {
+pub(crate) fn field_idx_ident(consts_prefix: &str, field_ident: &Ident) -> Ident {
     format_ident!(
-        "{}_IDX_{}",
-        struct_ident.to_string().to_shouty_snake_case(),
+        "{consts_prefix}_IDX_{}",
         field_ident.to_string().to_shouty_snake_case()
     )
 }
@@ -47,3 +97,45 @@ pub(crate) fn field_idx_ident(struct_ident: &Ident, field_ident: &Ident) -> Iden
 pub(crate) fn assoc_field_idx_ident(field_ident: &Ident) -> Ident {
     format_ident!("IDX_{}", field_ident.to_string().to_shouty_snake_case())
 }
+
+/// e.g. rgb_idx
+#[inline]
+pub(crate) fn consts_mod_ident(struct_ident: &Ident) -> Ident {
+    format_ident!("{}_idx", struct_ident.to_string().to_snake_case())
+}
+
+/// e.g. rgb as in rgb!{ r: 0, g: 0, b: 0 }
+#[inline]
+pub(crate) fn macro_rules_ident(struct_ident: &Ident) -> Ident {
+    format_ident!("{}", struct_ident.to_string().to_snake_case())
+}
+
+/// e.g. RgbField
+#[inline]
+pub(crate) fn field_enum_ident(struct_ident: &Ident) -> Ident {
+    format_ident!("{struct_ident}Field")
+}
+
+/// e.g. RgbFieldSet
+#[inline]
+pub(crate) fn fieldset_ident(struct_ident: &Ident) -> Ident {
+    format_ident!("{struct_ident}FieldSet")
+}
+
+/// e.g. RgbVec
+#[inline]
+pub(crate) fn soa_ident(struct_ident: &Ident) -> Ident {
+    format_ident!("{struct_ident}Vec")
+}
+
+/// e.g. R as in RgbField::R
+#[inline]
+pub(crate) fn field_variant_ident(field_ident: &Ident) -> Ident {
+    format_ident!("{}", field_ident.unraw().to_string().to_upper_camel_case())
+}
+
+/// e.g. R as in rgb_idx::R
+#[inline]
+pub(crate) fn mod_field_idx_ident(field_ident: &Ident) -> Ident {
+    format_ident!("{}", field_ident.to_string().to_shouty_snake_case())
+}
@@ -0,0 +1,116 @@
+use quote::{format_ident, quote};
+use syn::{Ident, Visibility};
+
+use crate::{args::INT_TYPES, idents::field_variant_ident, GenericArrayStructParams};
+
+/// Operations and the prefixes of the methods that core has for them.
+/// There is no `saturating_rem` since the remainder cannot overflow other than by wrapping
+const OPS: [(&str, &[&str]); 5] = [
+    ("add", &["checked", "saturating", "wrapping"]),
+    ("sub", &["checked", "saturating", "wrapping"]),
+    ("mul", &["checked", "saturating", "wrapping"]),
+    ("div", &["checked", "saturating", "wrapping"]),
+    ("rem", &["checked", "wrapping"]),
+];
+
+/// Methods generated on the struct
+pub(crate) fn methods() -> impl Iterator<Item = String> {
+    OPS.into_iter()
+        .flat_map(|(op, kinds)| kinds.iter().map(move |kind| format!("{kind}_{op}")))
+}
+
+/// Outputs the token stream to append
+pub(crate) fn impl_int_ops(
+    params: &GenericArrayStructParams,
+    struct_vis: &Visibility,
+) -> proc_macro2::TokenStream {
+    let struct_id = params.struct_ident();
+    let enum_id = params.field_enum_ident();
+    let int_types = &params.args().int_types;
+    let int_types: Vec<Ident> = if int_types.is_empty() {
+        INT_TYPES
+            .iter()
+            .map(|t| Ident::new(t, struct_id.span()))
+            .collect()
+    } else {
+        int_types.clone()
+    };
+
+    // in array index order
+    let variants: Vec<_> = params
+        .fields_by_idx()
+        .into_iter()
+        .map(|f| field_variant_ident(f.ident()))
+        .collect();
+    let a: Vec<_> = (0..variants.len()).map(|i| format_ident!("a{i}")).collect();
+    let b: Vec<_> = (0..variants.len()).map(|i| format_ident!("b{i}")).collect();
+
+    let mut methods = quote! {};
+    for (op, kinds) in OPS {
+        for kind in kinds {
+            let method = format_ident!("{kind}_{op}", span = struct_id.span());
+            methods.extend(if *kind == "checked" {
+                let doc = format!(
+                    "Elementwise `{method}`. \
+                    Errors with the first field, in array index order, \
+                    whose result overflowed{}.",
+                    if matches!(op, "div" | "rem") {
+                        " or that was divided by zero"
+                    } else {
+                        ""
+                    }
+                );
+                quote! {
+                    #[doc = #doc]
+                    #[inline]
+                    #struct_vis const fn #method(
+                        self,
+                        rhs: Self,
+                    ) -> Result<Self, ::generic_array_struct_rt::OverflowAt<#enum_id>> {
+                        let Self([#(#a),*]) = self;
+                        let Self([#(#b),*]) = rhs;
+                        Ok(Self([#(
+                            match #a.#method(#b) {
+                                Some(v) => v,
+                                None => {
+                                    return Err(::generic_array_struct_rt::OverflowAt {
+                                        field: #enum_id::#variants,
+                                    })
+                                }
+                            }
+                        ),*]))
+                    }
+                }
+            } else {
+                let doc = format!(
+                    "Elementwise `{method}`{}",
+                    if matches!(op, "div" | "rem") {
+                        ". Panics if any field of `rhs` is zero."
+                    } else {
+                        ""
+                    }
+                );
+                quote! {
+                    #[doc = #doc]
+                    #[inline]
+                    #struct_vis const fn #method(self, rhs: Self) -> Self {
+                        let Self([#(#a),*]) = self;
+                        let Self([#(#b),*]) = rhs;
+                        Self([#(#a.#method(#b)),*])
+                    }
+                }
+            });
+        }
+    }
+
+    let impls = int_types.iter().map(|ty| {
+        quote! {
+            impl #struct_id<#ty> {
+                #methods
+            }
+        }
+    });
+    quote! {
+        #(#impls)*
+    }
+}
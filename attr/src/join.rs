@@ -0,0 +1,89 @@
+use quote::quote;
+use syn::Visibility;
+
+use crate::GenericArrayStructParams;
+
+/// Methods generated on the struct
+pub(crate) const METHODS: &[&str] = &["join", "try_join"];
+
+/// Outputs the token stream to append
+pub(crate) fn impl_join(
+    params: &GenericArrayStructParams,
+    struct_vis: &Visibility,
+) -> proc_macro2::TokenStream {
+    let n_fields = params.fields().len();
+    let struct_id = params.struct_ident();
+
+    // Polls every future that has not completed yet, in array index order,
+    // storing its output in `outs`. Expects `futs`, `outs` and `cx` in scope.
+    // `on_ready` handles the output of a future that just completed.
+    let poll_all = |on_ready: proc_macro2::TokenStream| {
+        quote! {
+            let mut pending = false;
+            for (i, out) in outs.iter_mut().enumerate() {
+                if out.is_some() {
+                    continue;
+                }
+                // SAFETY: the elements of the pinned array are structurally pinned,
+                // they are never moved out of it
+                let fut = unsafe { futs.as_mut().map_unchecked_mut(|futs| &mut futs[i]) };
+                match core::future::Future::poll(fut, cx) {
+                    core::task::Poll::Ready(o) => *out = Some(#on_ready),
+                    core::task::Poll::Pending => pending = true,
+                }
+            }
+            if pending {
+                return core::task::Poll::Pending;
+            }
+        }
+    };
+    let join_poll = poll_all(quote! { o });
+    let try_join_poll = poll_all(quote! {
+        match o {
+            Ok(o) => o,
+            Err(e) => return core::task::Poll::Ready(Err(e)),
+        }
+    });
+    let take_outs = quote! {
+        #struct_id(core::array::from_fn(|i| match outs[i].take() {
+            Some(o) => o,
+            None => unreachable!(),
+        }))
+    };
+
+    quote! {
+        impl<F: core::future::Future> #struct_id<F> {
+            /// Polls all fields' futures concurrently in a single future,
+            /// completing with all their outputs once all of them have completed
+            #[inline]
+            #struct_vis fn join(self) -> impl core::future::Future<Output = #struct_id<F::Output>> {
+                async move {
+                    let mut futs = core::pin::pin!(self.0);
+                    let mut outs: [Option<F::Output>; #n_fields] = core::array::from_fn(|_| None);
+                    core::future::poll_fn(move |cx| {
+                        #join_poll
+                        core::task::Poll::Ready(#take_outs)
+                    })
+                    .await
+                }
+            }
+        }
+
+        impl<T, E, F: core::future::Future<Output = Result<T, E>>> #struct_id<F> {
+            /// [`Self::join`], but completes early with the first error,
+            /// dropping the fields' futures that have not completed yet
+            #[inline]
+            #struct_vis fn try_join(self) -> impl core::future::Future<Output = Result<#struct_id<T>, E>> {
+                async move {
+                    let mut futs = core::pin::pin!(self.0);
+                    let mut outs: [Option<T>; #n_fields] = core::array::from_fn(|_| None);
+                    core::future::poll_fn(move |cx| {
+                        #try_join_poll
+                        core::task::Poll::Ready(Ok(#take_outs))
+                    })
+                    .await
+                }
+            }
+        }
+    }
+}
@@ -1,321 +1,756 @@
 #![doc = include_str!("../README.md")]
 
+use args::{AttrArgs, ConstsMode, Flags};
 use builder::impl_builder;
+use collisions::check_collisions;
 use destr::impl_destr;
 use errs::{
     panic_only_works_with_structs, panic_only_works_with_structs_with_named_fields,
     panic_req_all_fields_same_generic, panic_req_single_generic,
 };
+use fields::{assign_indices, ArrayField, ExtraField, ParsedField};
 use idents::{
-    array_len_ident, const_with_ident, field_idx_ident, ident_mut, set_ident, with_ident,
+    array_len_ident, builder_ident, consts_mod_ident, consts_prefix, destr_ident, field_enum_ident,
+    field_idx_ident, fieldset_ident, macro_rules_ident, mod_field_idx_ident, new_builder_ident,
+    soa_ident,
 };
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote, ToTokens};
 use syn::{
-    parse::{Parse, ParseStream},
-    parse_macro_input,
+    parse_macro_input, parse_quote,
     token::{Bracket, Paren, Semi},
-    Attribute, Data, DataStruct, DeriveInput, Expr, ExprPath, Field, Fields, FieldsNamed,
-    FieldsUnnamed, GenericParam, Ident, Type, TypeArray, TypePath, Visibility,
+    Attribute, Data, DataStruct, DeriveInput, Field, Fields, FieldsNamed, FieldsUnnamed,
+    GenericParam, Generics, Ident, Type, TypeArray, TypePath, Visibility, WherePredicate,
+};
+use utils::{child_mod_vis, path_from_ident};
+
+use crate::{
+    ctor::impl_ctor, field_enum::impl_field_enum, fieldset::impl_fieldset,
+    idents::assoc_field_idx_ident, int_ops::impl_int_ops, join::impl_join,
+    macro_rules::impl_macro_rules, mask::impl_mask, ops::impl_ops, patch::impl_patch,
+    reduce::impl_reduce, soa::impl_soa, transpose::impl_transpose, trymap::impl_trymap,
+    zip::impl_zip,
 };
-use utils::path_from_ident;
-
-use crate::{idents::assoc_field_idx_ident, trymap::impl_trymap, zip::impl_zip};
 
+mod args;
 mod builder;
+mod collisions;
+mod ctor;
+mod derive;
 mod destr;
 mod errs;
+mod field_enum;
+mod fields;
+mod fieldset;
 mod idents;
+mod int_ops;
+mod join;
+mod macro_rules;
+mod mask;
+mod ops;
+mod patch;
+mod reduce;
+mod soa;
+mod transpose;
 mod trymap;
 mod utils;
 mod zip;
 
 const MACRO_NAME: &str = "generic_array_struct";
 
-#[repr(transparent)]
-struct GenericArrayStructParams(DeriveInput);
+struct GenericArrayStructParams {
+    input: DeriveInput,
+    args: AttrArgs,
+
+    /// Type param of the array elements e.g. `T`
+    generic: Ident,
+
+    fields: Vec<ArrayField>,
+    extras: Vec<ExtraField>,
+
+    /// Fields of the original struct with `#[gas(...)]` attributes removed
+    og_fields: FieldsNamed,
+}
+
+impl GenericArrayStructParams {
+    /// Checks and parses the fields of the input struct,
+    /// removing all `#[gas(...)]` field attributes from it
+    pub fn new(mut input: DeriveInput, args: AttrArgs) -> syn::Result<Self> {
+        let parsed = fields_named_mut(&mut input)
+            .named
+            .iter_mut()
+            .map(ParsedField::new)
+            .collect::<syn::Result<Vec<_>>>()?;
+        let og_fields = fields_named_mut(&mut input).clone();
+        let (extras, fields): (Vec<_>, Vec<_>) = parsed.into_iter().partition(|f| f.is_extra());
+
+        let generic = elem_generic(&input.generics, fields.first().map(|f| f.ty())).clone();
+        let fields = fields
+            .into_iter()
+            .map(|field| {
+                let is_generic = match field.ty() {
+                    Type::Path(p) => p.qself.is_none() && p.path.is_ident(&generic),
+                    _ => false,
+                };
+                if !is_generic {
+                    panic_req_all_fields_same_generic();
+                }
+                field.into_array_field(&args.names)
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        // the array is at tuple index 0
+        let extras = extras
+            .into_iter()
+            .enumerate()
+            .map(|(i, field)| field.into_extra_field(i + 1, &args.names))
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let mut res = Self {
+            input,
+            args,
+            generic,
+            fields,
+            extras,
+            og_fields,
+        };
+        assign_indices(&mut res.fields)?;
+        Ok(res)
+    }
+
+    /// Errors if the struct has anything more than the array, i.e.
+    /// extra fields, more than one generic param, a where clause
+    /// or bounds on the generic param.
+    ///
+    /// `arg` is the name of the arg that requires this.
+    pub fn check_simple(&self, arg: &str) -> syn::Result<()> {
+        let generics = &self.input.generics;
+        if !self.extras.is_empty() || generics.params.len() != 1 || generics.where_clause.is_some()
+        {
+            return Err(syn::Error::new(
+                self.struct_ident().span(),
+                format!(
+                    "`{arg}` arg does not support structs with extra fields, \
+                    more than one generic param or a where clause"
+                ),
+            ));
+        }
+        match generics.params.first() {
+            Some(GenericParam::Type(param)) if !param.bounds.is_empty() => {
+                Err(syn::Error::new_spanned(
+                    &param.bounds,
+                    format!("`{arg}` arg does not support bounds on the generic param"),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
 
 /// Accessors
 impl GenericArrayStructParams {
     #[inline]
     pub fn struct_vis(&self) -> &Visibility {
-        &self.0.vis
+        &self.input.vis
     }
 
     #[inline]
     pub fn struct_ident(&self) -> &Ident {
-        &self.0.ident
+        &self.input.ident
     }
 
+    /// Type param of the array elements e.g. `T`
     #[inline]
     pub fn generic_ident(&self) -> &Ident {
-        let mut generic_iter = self.0.generics.params.iter();
-        let generic = match generic_iter.next() {
-            Some(GenericParam::Type(g)) => g,
-            _ => panic_req_single_generic(),
-        };
-        if generic_iter.next().is_some() {
-            panic_req_single_generic();
-        }
-        &generic.ident
+        &self.generic
     }
 
+    /// All generics of the struct, including [`Self::generic_ident`]
     #[inline]
-    pub fn data_struct(&self) -> &DataStruct {
-        match &self.0.data {
-            Data::Struct(ds) => ds,
-            _ => panic_only_works_with_structs(),
-        }
+    pub fn generics(&self) -> &Generics {
+        &self.input.generics
     }
 
     #[inline]
     pub fn data_struct_mut(&mut self) -> &mut DataStruct {
-        match &mut self.0.data {
-            Data::Struct(ds) => ds,
-            _ => panic_only_works_with_structs(),
-        }
+        data_struct_mut(&mut self.input)
     }
 
     #[inline]
-    pub fn fields_named(&self) -> &FieldsNamed {
-        match &self.data_struct().fields {
-            Fields::Named(f) => f,
-            _ => panic_only_works_with_structs_with_named_fields(),
-        }
+    pub fn args(&self) -> &AttrArgs {
+        &self.args
+    }
+
+    /// The fields stored in the array, in declaration order
+    #[inline]
+    pub fn fields(&self) -> &[ArrayField] {
+        &self.fields
+    }
+
+    /// The fields stored in the array, in array index order
+    pub fn fields_by_idx(&self) -> Vec<&ArrayField> {
+        let mut res: Vec<_> = self.fields.iter().collect();
+        res.sort_by_key(|f| f.idx());
+        res
+    }
+
+    /// Whether the field enum e.g. `RgbField` is generated,
+    /// either explicitly or because other args use it
+    pub fn emit_field_enum(&self) -> bool {
+        let Flags {
+            field_enum,
+            ctor,
+            int_ops,
+            reduce,
+            fieldset,
+            trymap,
+            ..
+        } = self.args.flags;
+        field_enum
+            || ctor
+            || int_ops
+            || reduce
+            || fieldset
+            || (trymap && (self.args.trymap_opts.alloc || self.args.trymap_opts.named))
+    }
+
+    /// Whether the `destr` arg's `const_from_destr()` and `const_into_destr()` are generated.
+    ///
+    /// Moving non-Copy extra fields out of a struct is not possible in const fns
+    /// since its destructor cannot be evaluated at compile-time
+    pub fn emit_const_destr(&self) -> bool {
+        self.extras.iter().all(|f| f.is_phantom())
+    }
+
+    /// Clone of [`Self::generics`] with `predicate` added to its where clause
+    pub fn generics_with_predicate(&self, predicate: WherePredicate) -> Generics {
+        let mut res = self.input.generics.clone();
+        res.make_where_clause().predicates.push(predicate);
+        res
+    }
+
+    /// The fields not stored in the array, in declaration order
+    #[inline]
+    pub fn extras(&self) -> &[ExtraField] {
+        &self.extras
+    }
+
+    /// Fields of the original struct, in declaration order,
+    /// with `#[gas(...)]` attributes removed
+    #[inline]
+    pub fn og_fields(&self) -> &FieldsNamed {
+        &self.og_fields
     }
 
     #[inline]
     pub fn attrs(&self) -> &[Attribute] {
-        &self.0.attrs
+        &self.input.attrs
     }
 }
 
-struct AttrArgs {
-    array_field_vis: Visibility,
-    flags: Flags,
-}
+/// Names of generated items
+impl GenericArrayStructParams {
+    /// e.g. `RGB` as in `RGB_LEN`
+    pub fn consts_prefix(&self) -> String {
+        match &self.args.names.consts_prefix {
+            Some(p) => p.to_string(),
+            None => consts_prefix(self.struct_ident()),
+        }
+    }
 
-// be pretty funny if this was a #[generic_array_struct]
-#[derive(Default)]
-struct Flags {
-    builder: bool,
-    destr: bool,
-    trymap: bool,
-    zip: bool,
-}
+    /// e.g. `RGB_LEN`
+    pub fn len_ident(&self) -> Ident {
+        array_len_ident(&self.consts_prefix())
+    }
 
-const FLAGS_LEN: usize = core::mem::size_of::<Flags>();
+    /// e.g. `RGB_IDX_R`
+    pub fn field_idx_ident(&self, field: &ArrayField) -> Ident {
+        field_idx_ident(&self.consts_prefix(), field.ident())
+    }
 
-fn set_flag_checked(r: &mut bool, name: &'static str) {
-    if *r {
-        panic!("`{name}` already set");
+    /// e.g. `rgb_idx`
+    pub fn consts_mod_ident(&self) -> Ident {
+        match &self.args.names.consts_mod {
+            Some(id) => id.clone(),
+            None => consts_mod_ident(self.struct_ident()),
+        }
     }
-    *r = true;
-}
 
-impl Parse for AttrArgs {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        let mut flags = Flags::default();
-        let Flags {
-            builder,
-            destr,
-            trymap,
-            zip,
-        } = &mut flags;
+    /// Expression that evaluates to the length of the array,
+    /// depending on where the consts are output.
+    /// e.g. `RGB_LEN`, `rgb_idx::LEN`, `3`
+    pub fn len_expr(&self) -> proc_macro2::TokenStream {
+        match self.args.consts {
+            ConstsMode::Free => self.len_ident().into_token_stream(),
+            ConstsMode::Mod => {
+                let mod_id = self.consts_mod_ident();
+                quote! { #mod_id::LEN }
+            }
+            ConstsMode::Assoc => {
+                let n = self.fields.len();
+                quote! { #n }
+            }
+        }
+    }
 
-        for i in 0..FLAGS_LEN {
-            if !input.peek(Ident) {
-                break;
+    /// Expression that evaluates to the array index of `field`,
+    /// depending on where the consts are output.
+    /// e.g. `RGB_IDX_R`, `rgb_idx::R`, `0`
+    pub fn field_idx_expr(&self, field: &ArrayField) -> proc_macro2::TokenStream {
+        match self.args.consts {
+            ConstsMode::Free => self.field_idx_ident(field).into_token_stream(),
+            ConstsMode::Mod => {
+                let mod_id = self.consts_mod_ident();
+                let idx_id = mod_field_idx_ident(field.ident());
+                quote! { #mod_id::#idx_id }
             }
+            ConstsMode::Assoc => {
+                let i = field.idx();
+                quote! { #i }
+            }
+        }
+    }
 
-            let id: Ident = input.parse()?;
-            // cant match here, ident is not str
-            if id == "all" {
-                if i != 0 {
-                    panic!("`all` must not be used with other args");
-                }
+    /// e.g. `RgbBuilder`
+    pub fn builder_ident(&self) -> Ident {
+        match &self.args.names.builder {
+            Some(id) => id.clone(),
+            None => builder_ident(self.struct_ident()),
+        }
+    }
 
-                *builder = true;
-                *destr = true;
-                *trymap = true;
-                *zip = true;
-
-                break;
-            } else if id == "builder" {
-                set_flag_checked(builder, "builder");
-            } else if id == "destr" {
-                set_flag_checked(destr, "destr");
-            } else if id == "trymap" {
-                set_flag_checked(trymap, "trymap");
-            } else if id == "zip" {
-                set_flag_checked(zip, "zip");
-            } else {
-                panic!("Expected one of [`all`, `builder`, `destr`, `trymap`, `zip`]")
-            }
+    /// e.g. `NewRgbBuilder`
+    pub fn new_builder_ident(&self) -> Ident {
+        match &self.args.names.new_builder {
+            Some(id) => id.clone(),
+            None => new_builder_ident(&self.builder_ident()),
         }
+    }
 
-        if input.is_empty() {
-            return Ok(Self {
-                array_field_vis: Visibility::Inherited,
-                flags,
-            });
+    /// e.g. `RgbDestr`
+    pub fn destr_ident(&self) -> Ident {
+        match &self.args.names.destr {
+            Some(id) => id.clone(),
+            None => destr_ident(self.struct_ident()),
         }
+    }
 
-        let array_field_vis = input.parse()?;
-        Ok(Self {
-            array_field_vis,
-            flags,
-        })
+    /// e.g. `RgbField`
+    pub fn field_enum_ident(&self) -> Ident {
+        match &self.args.names.field_enum {
+            Some(id) => id.clone(),
+            None => field_enum_ident(self.struct_ident()),
+        }
+    }
+
+    /// e.g. `RgbFieldSet`
+    pub fn fieldset_ident(&self) -> Ident {
+        match &self.args.names.fieldset {
+            Some(id) => id.clone(),
+            None => fieldset_ident(self.struct_ident()),
+        }
+    }
+
+    /// e.g. `RgbFieldSetIter`
+    pub fn fieldset_iter_ident(&self) -> Ident {
+        format_ident!("{}Iter", self.fieldset_ident())
+    }
+
+    /// e.g. `RgbVec`
+    pub fn soa_ident(&self) -> Ident {
+        match &self.args.names.soa {
+            Some(id) => id.clone(),
+            None => soa_ident(self.struct_ident()),
+        }
+    }
+
+    /// e.g. `rgb` as in `rgb!{ r: 0, g: 0, b: 0 }`
+    pub fn macro_rules_ident(&self) -> Ident {
+        match &self.args.names.macro_rules {
+            Some(id) => id.clone(),
+            None => macro_rules_ident(self.struct_ident()),
+        }
     }
 }
 
-/// The main attribute proc macro. See crate docs for usage.
-#[proc_macro_attribute]
-pub fn generic_array_struct(attr_arg: TokenStream, input: TokenStream) -> TokenStream {
-    let AttrArgs {
-        array_field_vis,
-        flags:
-            Flags {
-                builder,
-                destr,
-                trymap,
-                zip,
-            },
-    } = parse_macro_input!(attr_arg as AttrArgs);
+/// The type param of the array elements.
+///
+/// If the struct has a single type param, that is it.
+/// Otherwise, it is the type of the first field stored in the array.
+fn elem_generic<'a>(generics: &'a Generics, first_field_ty: Option<&Type>) -> &'a Ident {
+    let mut type_params = generics.type_params();
+    if let (Some(only), None) = (type_params.next(), type_params.next()) {
+        return &only.ident;
+    }
+    let id = match first_field_ty {
+        Some(Type::Path(p)) if p.qself.is_none() => p.path.get_ident(),
+        _ => None,
+    };
+    match id.and_then(|id| generics.type_params().find(|tp| tp.ident == *id)) {
+        Some(tp) => &tp.ident,
+        None => panic_req_single_generic(),
+    }
+}
 
-    let input = parse_macro_input!(input as DeriveInput);
-    let mut params = GenericArrayStructParams(input);
+#[inline]
+fn data_struct_mut(input: &mut DeriveInput) -> &mut DataStruct {
+    match &mut input.data {
+        Data::Struct(ds) => ds,
+        _ => panic_only_works_with_structs(),
+    }
+}
+
+#[inline]
+fn fields_named_mut(input: &mut DeriveInput) -> &mut FieldsNamed {
+    match &mut data_struct_mut(input).fields {
+        Fields::Named(f) => f,
+        _ => panic_only_works_with_structs_with_named_fields(),
+    }
+}
 
+/// Generates all items for the struct other than the struct itself
+fn expand(params: &GenericArrayStructParams) -> proc_macro2::TokenStream {
     let mut fields_idx_consts = quote! {};
+    let mut fields_idx_mod_consts = quote! {};
     let mut fields_idx_assoc_consts = quote! {};
     let mut accessor_mutator_impls = quote! {};
     let mut const_with_impls = quote! {};
-    let n_fields =
-        params
-            .fields_named()
-            .named
-            .iter()
-            .enumerate()
-            .fold(0usize, |n_fields, (i, field)| {
-                let expect_same_generic = match &field.ty {
-                    Type::Path(g) => g,
-                    _ => panic_req_all_fields_same_generic(),
-                };
-                if !expect_same_generic
-                    .path
-                    .get_ident()
-                    .map(|id| id == params.generic_ident())
-                    .unwrap_or(false)
-                {
-                    panic_req_all_fields_same_generic();
+    let generic = params.generic_ident();
+    let n_fields = params.fields().iter().fold(0usize, |n_fields, field| {
+        let i = field.idx();
+        let field_vis = field.vis();
+        let field_ident = field.ident();
+
+        // pub const RGB_IDX_R: usize = 0;
+        let idx_ident = params.field_idx_ident(field);
+        fields_idx_consts.extend(quote! {
+            #field_vis const #idx_ident: usize = #i;
+        });
+
+        // pub mod rgb_idx { pub const R: usize = 0; }
+        let mod_idx_ident = mod_field_idx_ident(field_ident);
+        let mod_field_vis = child_mod_vis(field_vis);
+        fields_idx_mod_consts.extend(quote! {
+            #mod_field_vis const #mod_idx_ident: usize = #i;
+        });
+
+        let idx_ident = params.field_idx_expr(field);
+
+        // associated consts
+        // pub const IDX_R: usize = 0;
+        let assoc_idx_ident = assoc_field_idx_ident(field_ident);
+        fields_idx_assoc_consts.extend(quote! {
+            #field_vis const #assoc_idx_ident: usize = #i;
+        });
+
+        // fn r(), r_mut(), set_r(), with_r()
+        if let Some(getter) = field.getter() {
+            // preserve attributes such as doc comments on getter method
+            let field_attrs = field.attrs();
+            accessor_mutator_impls.extend(quote! {
+                #(#field_attrs)*
+                #[inline]
+                #field_vis const fn #getter(&self) -> &#generic {
+                    &self.0[#idx_ident]
+                }
+            });
+        }
+        if let Some(id_mut) = field.getter_mut() {
+            accessor_mutator_impls.extend(quote! {
+                #[inline]
+                #field_vis const fn #id_mut(&mut self) -> &mut #generic {
+                    &mut self.0[#idx_ident]
+                }
+            });
+        }
+        if let Some(set_id) = field.setter() {
+            accessor_mutator_impls.extend(quote! {
+                /// Returns the old field value
+                #[inline]
+                #field_vis const fn #set_id(&mut self, val: #generic) -> #generic {
+                    core::mem::replace(&mut self.0[#idx_ident], val)
+                }
+            });
+        }
+        if let Some(with_id) = field.with() {
+            accessor_mutator_impls.extend(quote! {
+                #[inline]
+                #field_vis fn #with_id(mut self, val: #generic) -> Self {
+                    self.0[#idx_ident] = val;
+                    self
                 }
+            });
+        }
 
-                let field_vis = &field.vis;
-                // unwrap-safety: named field checked above
-                let field_ident = field.ident.as_ref().unwrap();
-
-                // pub const RGB_IDX_R: usize = 0;
-                let idx_ident = field_idx_ident(params.struct_ident(), field_ident);
-                fields_idx_consts.extend(quote! {
-                    #field_vis const #idx_ident: usize = #i;
-                });
-
-                // associated consts
-                // pub const IDX_R: usize = 0;
-                let assoc_idx_ident = assoc_field_idx_ident(field_ident);
-                fields_idx_assoc_consts.extend(quote! {
-                    #field_vis const #assoc_idx_ident: usize = #i;
-                });
-
-                // fn r(), r_mut(), set_r(), with_r()
-                let id_mut = ident_mut(field_ident);
-                let set_id = set_ident(field_ident);
-                let with_id = with_ident(field_ident);
-                // preserve attributes such as doc comments on getter method
-                let field_attrs = &field.attrs;
-                accessor_mutator_impls.extend(quote! {
-                    #(#field_attrs)*
-                    #[inline]
-                    #field_vis const fn #field_ident(&self) -> &T {
-                        &self.0[#idx_ident]
-                    }
-
-                    #[inline]
-                    #field_vis const fn #id_mut(&mut self) -> &mut T {
-                        &mut self.0[#idx_ident]
-                    }
-
-                    /// Returns the old field value
-                    #[inline]
-                    #field_vis const fn #set_id(&mut self, val: T) -> T {
-                        core::mem::replace(&mut self.0[#idx_ident], val)
-                    }
-
-                    #[inline]
-                    #field_vis fn #with_id(mut self, val: T) -> Self {
-                        self.0[#idx_ident] = val;
-                        self
-                    }
-                });
-
-                // fn const_with_r()
-                let const_with_id = const_with_ident(field_ident);
-                const_with_impls.extend(quote! {
-                    #[inline]
-                    #field_vis const fn #const_with_id(mut self, val: T) -> Self {
-                        self.0[#idx_ident] = val;
-                        self
-                    }
-                });
-
-                n_fields + 1
+        // fn const_with_r()
+        if let Some(const_with_id) = field.const_with() {
+            const_with_impls.extend(quote! {
+                #[inline]
+                #field_vis const fn #const_with_id(mut self, val: #generic) -> Self {
+                    self.0[#idx_ident] = val;
+                    self
+                }
             });
+        }
+
+        n_fields + 1
+    });
 
-    let len_ident = array_len_ident(params.struct_ident());
+    // fn source(), source_mut(), set_source(), with_source() for extra fields.
+    // No const_with since the old value may not be droppable in const contexts.
+    for field in params.extras() {
+        let methods = match field.methods() {
+            Some(m) => m,
+            None => continue,
+        };
+        let field_vis = methods.vis();
+        let tuple_idx = field.tuple_idx();
+        let ty = field.ty();
+        if let Some(getter) = methods.getter() {
+            accessor_mutator_impls.extend(quote! {
+                #[inline]
+                #field_vis const fn #getter(&self) -> &#ty {
+                    &self.#tuple_idx
+                }
+            });
+        }
+        if let Some(id_mut) = methods.getter_mut() {
+            accessor_mutator_impls.extend(quote! {
+                #[inline]
+                #field_vis const fn #id_mut(&mut self) -> &mut #ty {
+                    &mut self.#tuple_idx
+                }
+            });
+        }
+        if let Some(set_id) = methods.setter() {
+            accessor_mutator_impls.extend(quote! {
+                /// Returns the old field value
+                #[inline]
+                #field_vis const fn #set_id(&mut self, val: #ty) -> #ty {
+                    core::mem::replace(&mut self.#tuple_idx, val)
+                }
+            });
+        }
+        if let Some(with_id) = methods.with() {
+            accessor_mutator_impls.extend(quote! {
+                #[inline]
+                #field_vis fn #with_id(mut self, val: #ty) -> Self {
+                    self.#tuple_idx = val;
+                    self
+                }
+            });
+        }
+    }
 
     let struct_vis = params.struct_vis();
     let struct_ident = params.struct_ident();
-    let mut res = quote! {
-        #struct_vis const #len_ident: usize = #n_fields;
+    let mut res = match params.args().consts {
+        ConstsMode::Free => {
+            let len_ident = params.len_ident();
+            quote! {
+                #struct_vis const #len_ident: usize = #n_fields;
 
-        impl<T> #struct_ident<T> {
+                #fields_idx_consts
+            }
+        }
+        ConstsMode::Assoc => quote! {},
+        ConstsMode::Mod => {
+            let mod_ident = params.consts_mod_ident();
+            let mod_struct_vis = child_mod_vis(struct_vis);
+            let mod_doc = format!("Array length and field indices of [`{struct_ident}`]");
+            quote! {
+                #[doc = #mod_doc]
+                #struct_vis mod #mod_ident {
+                    #mod_struct_vis const LEN: usize = #n_fields;
+
+                    #fields_idx_mod_consts
+                }
+            }
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = params.generics().split_for_impl();
+    let copy_generics = params.generics_with_predicate(parse_quote! { #generic: Copy });
+    let (copy_impl_generics, _, copy_where_clause) = copy_generics.split_for_impl();
+    res.extend(quote! {
+        impl #impl_generics #struct_ident #ty_generics #where_clause {
             #accessor_mutator_impls
         }
 
-        impl<T: Copy> #struct_ident<T> {
+        impl #copy_impl_generics #struct_ident #ty_generics #copy_where_clause {
             #const_with_impls
         }
 
-        impl<T> #struct_ident<T> {
+        impl #impl_generics #struct_ident #ty_generics #where_clause {
             #struct_vis const LEN: usize = #n_fields;
 
             #fields_idx_assoc_consts
         }
+    });
 
-        #fields_idx_consts
-    };
+    let Flags {
+        builder,
+        destr,
+        trymap,
+        zip,
+        macro_rules,
+        ctor,
+        field_enum: _,
+        ops,
+        int_ops,
+        reduce,
+        mask,
+        fieldset,
+        patch,
+        r#async,
+        soa,
+        transpose,
+    } = params.args().flags;
 
     if builder {
-        res.extend(impl_builder(&params, struct_vis));
+        res.extend(impl_builder(params, struct_vis));
     }
 
     if destr {
-        res.extend(impl_destr(&params, struct_vis));
+        res.extend(impl_destr(params, struct_vis));
     }
 
     if trymap {
-        res.extend(impl_trymap(&params));
+        match params.check_simple("trymap") {
+            Ok(()) => res.extend(impl_trymap(params)),
+            Err(e) => res.extend(e.to_compile_error()),
+        }
     }
 
     if zip {
-        res.extend(impl_zip(&params));
+        match params.check_simple("zip") {
+            Ok(()) => res.extend(impl_zip(params)),
+            Err(e) => res.extend(e.to_compile_error()),
+        }
+    }
+
+    if macro_rules {
+        match params.check_simple("macro_rules") {
+            Ok(()) => res.extend(impl_macro_rules(params, struct_vis)),
+            Err(e) => res.extend(e.to_compile_error()),
+        }
+    }
+
+    if ctor {
+        match params.check_simple("ctor") {
+            Ok(()) => res.extend(impl_ctor(params, struct_vis)),
+            Err(e) => res.extend(e.to_compile_error()),
+        }
+    }
+
+    if ops {
+        match params.check_simple("ops") {
+            Ok(()) => res.extend(impl_ops(params)),
+            Err(e) => res.extend(e.to_compile_error()),
+        }
+    }
+
+    if int_ops {
+        match params.check_simple("int_ops") {
+            Ok(()) => res.extend(impl_int_ops(params, struct_vis)),
+            Err(e) => res.extend(e.to_compile_error()),
+        }
+    }
+
+    if reduce {
+        match params.check_simple("reduce") {
+            Ok(()) => res.extend(impl_reduce(params, struct_vis)),
+            Err(e) => res.extend(e.to_compile_error()),
+        }
+    }
+
+    if mask {
+        match params
+            .check_simple("mask")
+            .and_then(|()| impl_mask(params, struct_vis))
+        {
+            Ok(ts) => res.extend(ts),
+            Err(e) => res.extend(e.to_compile_error()),
+        }
+    }
+
+    if fieldset {
+        match params
+            .check_simple("fieldset")
+            .and_then(|()| impl_fieldset(params, struct_vis))
+        {
+            Ok(ts) => res.extend(ts),
+            Err(e) => res.extend(e.to_compile_error()),
+        }
+    }
+
+    if patch {
+        match params.check_simple("patch") {
+            Ok(()) => res.extend(impl_patch(params, struct_vis)),
+            Err(e) => res.extend(e.to_compile_error()),
+        }
+    }
+
+    if r#async {
+        match params.check_simple("async") {
+            Ok(()) => res.extend(impl_join(params, struct_vis)),
+            Err(e) => res.extend(e.to_compile_error()),
+        }
+    }
+
+    if soa {
+        match params.check_simple("soa") {
+            Ok(()) => res.extend(impl_soa(params, struct_vis)),
+            Err(e) => res.extend(e.to_compile_error()),
+        }
+    }
+
+    if transpose {
+        match params.check_simple("transpose") {
+            Ok(()) => res.extend(impl_transpose(params)),
+            Err(e) => res.extend(e.to_compile_error()),
+        }
+    }
+
+    if params.emit_field_enum() {
+        res.extend(impl_field_enum(params, struct_vis));
+    }
+
+    res
+}
+
+/// The main attribute proc macro. See crate docs for usage.
+#[proc_macro_attribute]
+pub fn generic_array_struct(attr_arg: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr_arg as AttrArgs);
+    let input = parse_macro_input!(input as DeriveInput);
+    let mut params = match GenericArrayStructParams::new(input, args) {
+        Ok(p) => p,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    if let Err(e) = check_collisions(&params) {
+        return e.to_compile_error().into();
     }
 
-    // finally, replace the struct defn with a single array field tuple struct
+    let mut res = expand(&params);
+
+    // finally, replace the struct defn with a tuple struct of the array field
+    // followed by the extra fields
+    let len_expr = params.len_expr();
+    let extra_fields: Vec<_> = params
+        .extras()
+        .iter()
+        .map(|f| Field {
+            vis: f.decl_vis().clone(),
+            attrs: f.attrs().to_vec(),
+            mutability: syn::FieldMutability::None,
+            ident: None,
+            colon_token: None,
+            ty: f.ty().clone(),
+        })
+        .collect();
     params.data_struct_mut().fields = Fields::Unnamed(FieldsUnnamed {
         paren_token: Paren::default(),
         unnamed: core::iter::once(Field {
-            vis: array_field_vis,
+            vis: params.args().array_field_vis.clone(),
             attrs: Vec::new(),
             mutability: syn::FieldMutability::None,
             ident: None,
@@ -327,19 +762,28 @@ pub fn generic_array_struct(attr_arg: TokenStream, input: TokenStream) -> TokenS
                     path: path_from_ident(params.generic_ident().clone()),
                 })),
                 semi_token: Semi::default(),
-                len: Expr::Path(ExprPath {
-                    attrs: Vec::new(),
-                    qself: None,
-                    path: path_from_ident(len_ident),
-                }),
+                len: parse_quote! { #len_expr },
             }),
         })
+        .chain(extra_fields)
         .collect(),
     });
 
     // extend with original input with modified struct defn
-    let GenericArrayStructParams(input) = params;
+    let GenericArrayStructParams { input, .. } = params;
     res.extend(quote! { #input });
 
     res.into()
 }
+
+/// Derive macro for existing single-array-field tuple structs,
+/// generating the same items as [`macro@generic_array_struct`]
+/// without changing the struct definition. See crate docs for usage.
+#[proc_macro_derive(ArrayFields, attributes(fields, gas))]
+pub fn derive_array_fields(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match derive::derive_array_fields(input) {
+        Ok(res) => res.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
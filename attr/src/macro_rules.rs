@@ -0,0 +1,100 @@
+use quote::{format_ident, quote};
+use syn::Visibility;
+
+use crate::GenericArrayStructParams;
+
+/// Methods generated on the struct
+pub(crate) const METHODS: &[&str] = &["__gas_from_array", "__gas_into_array"];
+
+/// Outputs the token stream to append
+pub(crate) fn impl_macro_rules(
+    params: &GenericArrayStructParams,
+    struct_vis: &Visibility,
+) -> proc_macro2::TokenStream {
+    let struct_id = params.struct_ident();
+    let macro_id = params.macro_rules_ident();
+    let len = params.len_expr();
+    let path = match &params.args().macro_path {
+        Some(p) => quote! { #p },
+        None => quote! { #struct_id },
+    };
+
+    // local struct that makes the compiler check for missing, duplicate and unknown fields
+    let fields_struct_id = format_ident!("{struct_id}Fields");
+    let decl_fields = params.fields().iter().map(|f| f.ident());
+    let [pat_fields, base_fields] = core::array::from_fn(|_| {
+        let fields = params.fields().iter().map(|f| f.ident());
+        quote! { #(#fields),* }
+    });
+    let [arr_fields, base_arr_fields] = core::array::from_fn(|_| {
+        let fields = params.fields_by_idx().into_iter().map(|f| f.ident());
+        quote! { #(#fields),* }
+    });
+    let fields_struct = quote! {
+        struct #fields_struct_id<T> {
+            #(#decl_fields: T),*
+        }
+    };
+
+    // macro_rules cannot be `pub` without #[macro_export],
+    // so reexport it with at most `pub(crate)` visibility
+    let use_vis = match struct_vis {
+        Visibility::Inherited => None,
+        Visibility::Public(_) => Some(quote! { pub(crate) }),
+        vis => Some(quote! { #vis }),
+    };
+    let reexport = use_vis.map(|vis| {
+        quote! {
+            #[allow(unused_imports)]
+            #vis use #macro_id;
+        }
+    });
+
+    let doc = format!(
+        "Constructs a [`{struct_id}`] from named fields in any order, \
+        optionally followed by `..base` to take the remaining fields from `base`"
+    );
+
+    quote! {
+        impl<T> #struct_id <T> {
+            #[doc(hidden)]
+            #[inline]
+            #struct_vis const fn __gas_from_array(arr: [T; #len]) -> Self {
+                Self(arr)
+            }
+
+            #[doc(hidden)]
+            #[inline]
+            #struct_vis const fn __gas_into_array(self) -> [T; #len] {
+                // cannot move out of self.0 in const fns since Self may have a destructor
+                let this = core::mem::ManuallyDrop::new(self);
+                let ptr: *const Self = (&this as *const core::mem::ManuallyDrop<Self>).cast();
+                unsafe { core::ptr::read(core::ptr::addr_of!((*ptr).0)) }
+            }
+        }
+
+        #[doc = #doc]
+        #[allow(unused_macros)]
+        macro_rules! #macro_id {
+            ($($field:ident : $val:expr),* $(,)?) => {{
+                #fields_struct
+                let #fields_struct_id { #pat_fields } = #fields_struct_id { $($field: $val),* };
+                #path::__gas_from_array([#arr_fields])
+            }};
+            ($($field:ident : $val:expr,)* ..$base:expr) => {{
+                #fields_struct
+                #[allow(clippy::needless_update)]
+                let #fields_struct_id { #pat_fields } = #fields_struct_id {
+                    $($field: $val,)*
+                    ..{
+                        let [#base_arr_fields] = #path::__gas_into_array($base);
+                        #fields_struct_id { #base_fields }
+                    }
+                };
+                #path::__gas_from_array([#arr_fields])
+            }};
+        }
+
+        #reexport
+    }
+}
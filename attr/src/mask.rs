@@ -0,0 +1,108 @@
+use quote::{format_ident, quote};
+use syn::Visibility;
+
+use crate::{utils::bits_ty, GenericArrayStructParams};
+
+/// Methods generated on the struct
+pub(crate) const METHODS: &[&str] = &[
+    "any",
+    "all",
+    "count",
+    "to_bits",
+    "from_bits",
+    "select",
+    "lt_mask",
+    "le_mask",
+    "gt_mask",
+    "ge_mask",
+    "eq_mask",
+    "ne_mask",
+];
+
+/// Outputs the token stream to append
+pub(crate) fn impl_mask(
+    params: &GenericArrayStructParams,
+    struct_vis: &Visibility,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let n_fields = params.fields().len();
+    let struct_id = params.struct_ident();
+
+    let bits_ty = bits_ty(n_fields, struct_id.span()).ok_or_else(|| {
+        syn::Error::new(
+            struct_id.span(),
+            "`mask` arg supports structs with at most 128 fields",
+        )
+    })?;
+
+    // array index order
+    let idxs: Vec<_> = (0..n_fields).collect();
+    let a: Vec<_> = idxs.iter().map(|i| format_ident!("a{i}")).collect();
+    let b: Vec<_> = idxs.iter().map(|i| format_ident!("b{i}")).collect();
+
+    let cmps = [
+        ("lt_mask", quote! { < }, quote! { PartialOrd }),
+        ("le_mask", quote! { <= }, quote! { PartialOrd }),
+        ("gt_mask", quote! { > }, quote! { PartialOrd }),
+        ("ge_mask", quote! { >= }, quote! { PartialOrd }),
+        ("eq_mask", quote! { == }, quote! { PartialEq }),
+        ("ne_mask", quote! { != }, quote! { PartialEq }),
+    ]
+    .map(|(name, op, tr)| {
+        let name = format_ident!("{name}", span = struct_id.span());
+        let doc = format!("Elementwise `self {op} other`");
+        quote! {
+            #[doc = #doc]
+            #[inline]
+            #struct_vis fn #name(&self, other: &Self) -> #struct_id<bool> where T: #tr {
+                #struct_id([#(self.0[#idxs] #op other.0[#idxs]),*])
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #struct_id<bool> {
+            /// Whether any field is `true`
+            #[inline]
+            #struct_vis const fn any(&self) -> bool {
+                false #(|| self.0[#idxs])*
+            }
+
+            /// Whether all fields are `true`
+            #[inline]
+            #struct_vis const fn all(&self) -> bool {
+                true #(&& self.0[#idxs])*
+            }
+
+            /// Number of `true` fields
+            #[inline]
+            #struct_vis const fn count(&self) -> usize {
+                0 #(+ self.0[#idxs] as usize)*
+            }
+
+            /// Bit `i` is set if the field at array index `i` is `true`
+            #[inline]
+            #struct_vis const fn to_bits(&self) -> #bits_ty {
+                0 #(| ((self.0[#idxs] as #bits_ty) << #idxs))*
+            }
+
+            /// The field at array index `i` is `true` if bit `i` is set.
+            /// Bits at or above the number of fields are ignored.
+            #[inline]
+            #struct_vis const fn from_bits(bits: #bits_ty) -> Self {
+                Self([#(bits & (1 << #idxs) != 0),*])
+            }
+
+            /// Fields of `a` where `self` is `true`, fields of `b` otherwise
+            #[inline]
+            #struct_vis fn select<T>(self, a: #struct_id<T>, b: #struct_id<T>) -> #struct_id<T> {
+                let #struct_id([#(#a),*]) = a;
+                let #struct_id([#(#b),*]) = b;
+                #struct_id([#(if self.0[#idxs] { #a } else { #b }),*])
+            }
+        }
+
+        impl<T> #struct_id<T> {
+            #(#cmps)*
+        }
+    })
+}
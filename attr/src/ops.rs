@@ -0,0 +1,159 @@
+use quote::{format_ident, quote};
+use syn::Ident;
+
+use crate::GenericArrayStructParams;
+
+/// `(trait, method, assign trait, assign method)` of the binary operators
+const BIN_OPS: [(&str, &str, &str, &str); 5] = [
+    ("Add", "add", "AddAssign", "add_assign"),
+    ("Sub", "sub", "SubAssign", "sub_assign"),
+    ("Mul", "mul", "MulAssign", "mul_assign"),
+    ("Div", "div", "DivAssign", "div_assign"),
+    ("Rem", "rem", "RemAssign", "rem_assign"),
+];
+
+/// Outputs the token stream to append
+pub(crate) fn impl_ops(params: &GenericArrayStructParams) -> proc_macro2::TokenStream {
+    let n_fields = params.fields().len();
+    let struct_id = params.struct_ident();
+
+    // Operates on each element of the arrays directly
+    // instead of through the accessor methods so that the compiler can auto-vectorize.
+    // Elements are in array index order, indices are usize literals.
+    let idxs: Vec<_> = (0..n_fields).collect();
+    let a: Vec<_> = idxs.iter().map(|i| format_ident!("a{i}")).collect();
+    let b: Vec<_> = idxs.iter().map(|i| format_ident!("b{i}")).collect();
+
+    let mut res = quote! {};
+    for (tr, method, tr_assign, method_assign) in BIN_OPS {
+        let [tr, method, tr_assign, method_assign] =
+            [tr, method, tr_assign, method_assign].map(|s| Ident::new(s, struct_id.span()));
+        res.extend(quote! {
+            impl<T> core::ops::#tr for #struct_id<T> where T: core::ops::#tr<Output = T> {
+                type Output = Self;
+
+                #[inline]
+                fn #method(self, rhs: Self) -> Self {
+                    let Self([#(#a),*]) = self;
+                    let Self([#(#b),*]) = rhs;
+                    Self([#(core::ops::#tr::#method(#a, #b)),*])
+                }
+            }
+
+            impl<'b, T> core::ops::#tr<&'b #struct_id<T>> for #struct_id<T>
+                where T: core::ops::#tr<&'b T, Output = T>
+            {
+                type Output = Self;
+
+                #[inline]
+                fn #method(self, rhs: &'b #struct_id<T>) -> Self {
+                    let Self([#(#a),*]) = self;
+                    Self([#(core::ops::#tr::#method(#a, &rhs.0[#idxs])),*])
+                }
+            }
+
+            impl<'a, T> core::ops::#tr<#struct_id<T>> for &'a #struct_id<T>
+                where &'a T: core::ops::#tr<T, Output = T>
+            {
+                type Output = #struct_id<T>;
+
+                #[inline]
+                fn #method(self, rhs: #struct_id<T>) -> #struct_id<T> {
+                    let #struct_id([#(#b),*]) = rhs;
+                    #struct_id([#(core::ops::#tr::#method(&self.0[#idxs], #b)),*])
+                }
+            }
+
+            impl<'a, 'b, T> core::ops::#tr<&'b #struct_id<T>> for &'a #struct_id<T>
+                where &'a T: core::ops::#tr<&'b T, Output = T>
+            {
+                type Output = #struct_id<T>;
+
+                #[inline]
+                fn #method(self, rhs: &'b #struct_id<T>) -> #struct_id<T> {
+                    #struct_id([#(core::ops::#tr::#method(&self.0[#idxs], &rhs.0[#idxs])),*])
+                }
+            }
+
+            // scalar, applied to every element.
+            // Does not overlap with the impls above since `T` cannot be `Self`
+
+            impl<T> core::ops::#tr<T> for #struct_id<T> where T: core::ops::#tr<Output = T> + Copy {
+                type Output = Self;
+
+                #[inline]
+                fn #method(self, rhs: T) -> Self {
+                    let Self([#(#a),*]) = self;
+                    Self([#(core::ops::#tr::#method(#a, rhs)),*])
+                }
+            }
+
+            impl<'a, T> core::ops::#tr<T> for &'a #struct_id<T>
+                where &'a T: core::ops::#tr<T, Output = T>, T: Copy
+            {
+                type Output = #struct_id<T>;
+
+                #[inline]
+                fn #method(self, rhs: T) -> #struct_id<T> {
+                    #struct_id([#(core::ops::#tr::#method(&self.0[#idxs], rhs)),*])
+                }
+            }
+
+            impl<T> core::ops::#tr_assign for #struct_id<T> where T: core::ops::#tr_assign {
+                #[inline]
+                fn #method_assign(&mut self, rhs: Self) {
+                    self.0
+                        .iter_mut()
+                        .zip(rhs.0)
+                        .for_each(|(a, b)| core::ops::#tr_assign::#method_assign(a, b));
+                }
+            }
+
+            impl<'b, T> core::ops::#tr_assign<&'b #struct_id<T>> for #struct_id<T>
+                where T: core::ops::#tr_assign<&'b T>
+            {
+                #[inline]
+                fn #method_assign(&mut self, rhs: &'b #struct_id<T>) {
+                    self.0
+                        .iter_mut()
+                        .zip(rhs.0.iter())
+                        .for_each(|(a, b)| core::ops::#tr_assign::#method_assign(a, b));
+                }
+            }
+
+            impl<T> core::ops::#tr_assign<T> for #struct_id<T>
+                where T: core::ops::#tr_assign + Copy
+            {
+                #[inline]
+                fn #method_assign(&mut self, rhs: T) {
+                    self.0
+                        .iter_mut()
+                        .for_each(|a| core::ops::#tr_assign::#method_assign(a, rhs));
+                }
+            }
+        });
+    }
+
+    res.extend(quote! {
+        impl<T> core::ops::Neg for #struct_id<T> where T: core::ops::Neg<Output = T> {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                let Self([#(#a),*]) = self;
+                Self([#(core::ops::Neg::neg(#a)),*])
+            }
+        }
+
+        impl<'a, T> core::ops::Neg for &'a #struct_id<T> where &'a T: core::ops::Neg<Output = T> {
+            type Output = #struct_id<T>;
+
+            #[inline]
+            fn neg(self) -> #struct_id<T> {
+                #struct_id([#(core::ops::Neg::neg(&self.0[#idxs])),*])
+            }
+        }
+    });
+
+    res
+}
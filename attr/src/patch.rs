@@ -0,0 +1,68 @@
+use quote::{format_ident, quote};
+use syn::Visibility;
+
+use crate::GenericArrayStructParams;
+
+/// Methods generated on the struct
+pub(crate) const METHODS: &[&str] = &["apply_to", "merge", "is_empty", "patch_from"];
+
+/// Outputs the token stream to append
+pub(crate) fn impl_patch(
+    params: &GenericArrayStructParams,
+    struct_vis: &Visibility,
+) -> proc_macro2::TokenStream {
+    let n_fields = params.fields().len();
+    let struct_id = params.struct_ident();
+
+    // array index order
+    let idxs: Vec<_> = (0..n_fields).collect();
+    let a: Vec<_> = idxs.iter().map(|i| format_ident!("a{i}")).collect();
+    let b: Vec<_> = idxs.iter().map(|i| format_ident!("b{i}")).collect();
+
+    quote! {
+        impl<T> #struct_id<Option<T>> {
+            /// Overwrites the fields of `target` that are `Some` in `self`
+            #[inline]
+            #struct_vis fn apply_to(self, target: &mut #struct_id<T>) {
+                let Self([#(#a),*]) = self;
+                #(
+                    if let Some(v) = #a {
+                        target.0[#idxs] = v;
+                    }
+                )*
+            }
+
+            /// Combines two patches, with fields that are `Some` in `other` taking precedence,
+            /// so that applying the result is the same as applying `self` then `other`
+            #[inline]
+            #struct_vis fn merge(self, other: Self) -> Self {
+                let Self([#(#a),*]) = self;
+                let Self([#(#b),*]) = other;
+                Self([#(#b.or(#a)),*])
+            }
+
+            /// Whether all fields are `None`, i.e. applying the patch is a no-op
+            #[inline]
+            #struct_vis const fn is_empty(&self) -> bool {
+                true #(&& self.0[#idxs].is_none())*
+            }
+        }
+
+        impl<T> #struct_id<T> {
+            /// The patch that turns `old` into `new`,
+            /// with fields that are equal in both set to `None`
+            #[inline]
+            #struct_vis fn patch_from(old: &Self, new: &Self) -> #struct_id<Option<T>>
+                where T: PartialEq + Clone
+            {
+                #struct_id([#(
+                    if old.0[#idxs] == new.0[#idxs] {
+                        None
+                    } else {
+                        Some(new.0[#idxs].clone())
+                    }
+                ),*])
+            }
+        }
+    }
+}
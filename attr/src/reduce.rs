@@ -0,0 +1,161 @@
+use quote::{format_ident, quote};
+use syn::Visibility;
+
+use crate::GenericArrayStructParams;
+
+/// Methods generated on the struct
+pub(crate) const METHODS: &[&str] = &[
+    "fold_fields",
+    "sum_fields",
+    "product_fields",
+    "min_field",
+    "max_field",
+    "argmin",
+    "argmax",
+];
+
+/// Outputs the token stream to append
+pub(crate) fn impl_reduce(
+    params: &GenericArrayStructParams,
+    struct_vis: &Visibility,
+) -> proc_macro2::TokenStream {
+    let n_fields = params.fields().len();
+    let struct_id = params.struct_ident();
+    let enum_id = params.field_enum_ident();
+
+    let idxs: Vec<_> = (0..n_fields).collect();
+    let a: Vec<_> = idxs.iter().map(|i| format_ident!("a{i}")).collect();
+    let b: Vec<_> = idxs.iter().map(|i| format_ident!("b{i}")).collect();
+
+    let mut res = quote! {
+        impl<T> core::iter::Sum for #struct_id<T>
+            where T: core::iter::Sum + core::ops::Add<Output = T>
+        {
+            #[inline]
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(
+                    Self(core::array::from_fn(|_| core::iter::empty::<T>().sum())),
+                    |Self([#(#a),*]), Self([#(#b),*])| Self([#(#a + #b),*]),
+                )
+            }
+        }
+
+        impl<'a, T: 'a> core::iter::Sum<&'a #struct_id<T>> for #struct_id<T>
+            where T: core::iter::Sum<&'a T> + core::ops::Add<&'a T, Output = T>
+        {
+            #[inline]
+            fn sum<I: Iterator<Item = &'a #struct_id<T>>>(iter: I) -> Self {
+                iter.fold(
+                    Self(core::array::from_fn(|_| core::iter::empty::<&'a T>().sum())),
+                    |Self([#(#a),*]), x| Self([#(#a + &x.0[#idxs]),*]),
+                )
+            }
+        }
+
+        impl<T> core::iter::Product for #struct_id<T>
+            where T: core::iter::Product + core::ops::Mul<Output = T>
+        {
+            #[inline]
+            fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(
+                    Self(core::array::from_fn(|_| core::iter::empty::<T>().product())),
+                    |Self([#(#a),*]), Self([#(#b),*])| Self([#(#a * #b),*]),
+                )
+            }
+        }
+
+        impl<'a, T: 'a> core::iter::Product<&'a #struct_id<T>> for #struct_id<T>
+            where T: core::iter::Product<&'a T> + core::ops::Mul<&'a T, Output = T>
+        {
+            #[inline]
+            fn product<I: Iterator<Item = &'a #struct_id<T>>>(iter: I) -> Self {
+                iter.fold(
+                    Self(core::array::from_fn(|_| core::iter::empty::<&'a T>().product())),
+                    |Self([#(#a),*]), x| Self([#(#a * &x.0[#idxs]),*]),
+                )
+            }
+        }
+
+        impl<T> #struct_id<T> {
+            /// Folds the fields in array index order
+            #[inline]
+            #struct_vis fn fold_fields<B, F>(self, init: B, mut f: F) -> B
+                where F: FnMut(B, #enum_id, T) -> B
+            {
+                #enum_id::ALL
+                    .into_iter()
+                    .zip(self.0)
+                    .fold(init, |acc, (field, val)| f(acc, field, val))
+            }
+
+            #[inline]
+            #struct_vis fn sum_fields(self) -> T where T: core::iter::Sum {
+                self.0.into_iter().sum()
+            }
+
+            #[inline]
+            #struct_vis fn product_fields(self) -> T where T: core::iter::Product {
+                self.0.into_iter().product()
+            }
+        }
+    };
+
+    // there is no min or max of zero fields
+    if n_fields > 0 {
+        res.extend(quote! {
+            impl<T> #struct_id<T> {
+                /// Returns the first field in array index order if there are several minimums.
+                ///
+                /// Fields that are not comparable to themselves e.g. NaN
+                /// are skipped unless all fields are.
+                #[inline]
+                #struct_vis fn min_field(&self) -> (#enum_id, &T) where T: PartialOrd {
+                    #enum_id::ALL
+                        .into_iter()
+                        .zip(self.0.iter())
+                        .reduce(|min, x| match x.1.partial_cmp(min.1) {
+                            Some(core::cmp::Ordering::Less) => x,
+                            None if min.1.partial_cmp(min.1).is_none()
+                                && x.1.partial_cmp(x.1).is_some() => x,
+                            _ => min,
+                        })
+                        // unwrap-safety: at least one field
+                        .unwrap()
+                }
+
+                /// Returns the first field in array index order if there are several maximums.
+                ///
+                /// Fields that are not comparable to themselves e.g. NaN
+                /// are skipped unless all fields are.
+                #[inline]
+                #struct_vis fn max_field(&self) -> (#enum_id, &T) where T: PartialOrd {
+                    #enum_id::ALL
+                        .into_iter()
+                        .zip(self.0.iter())
+                        .reduce(|max, x| match x.1.partial_cmp(max.1) {
+                            Some(core::cmp::Ordering::Greater) => x,
+                            None if max.1.partial_cmp(max.1).is_none()
+                                && x.1.partial_cmp(x.1).is_some() => x,
+                            _ => max,
+                        })
+                        // unwrap-safety: at least one field
+                        .unwrap()
+                }
+
+                /// Returns the first field in array index order if there are several minimums
+                #[inline]
+                #struct_vis fn argmin(&self) -> #enum_id where T: PartialOrd {
+                    self.min_field().0
+                }
+
+                /// Returns the first field in array index order if there are several maximums
+                #[inline]
+                #struct_vis fn argmax(&self) -> #enum_id where T: PartialOrd {
+                    self.max_field().0
+                }
+            }
+        });
+    }
+
+    res
+}
@@ -0,0 +1,155 @@
+use quote::{format_ident, quote};
+use syn::Visibility;
+
+use crate::{
+    idents::{col_ident, ident_mut},
+    GenericArrayStructParams,
+};
+
+/// Outputs the token stream to append
+pub(crate) fn impl_soa(
+    params: &GenericArrayStructParams,
+    struct_vis: &Visibility,
+) -> proc_macro2::TokenStream {
+    let n_fields = params.fields().len();
+    let struct_id = params.struct_ident();
+    let soa_id = params.soa_ident();
+
+    // array index order
+    let idxs: Vec<_> = (0..n_fields).collect();
+    let a: Vec<_> = idxs.iter().map(|i| format_ident!("a{i}")).collect();
+    let c: Vec<_> = idxs.iter().map(|i| format_ident!("c{i}")).collect();
+
+    let cols = params.fields().iter().map(|f| {
+        let col = col_ident(f.ident());
+        let col_mut = ident_mut(&col);
+        let idx = params.field_idx_expr(f);
+        let doc = format!("The column of the `{}` field", f.ident());
+        let doc_mut = format!(
+            "The column of the `{}` field. Its length cannot be changed through this",
+            f.ident()
+        );
+        quote! {
+            #[doc = #doc]
+            #[inline]
+            #struct_vis fn #col(&self) -> &[T] {
+                &self.0[#idx]
+            }
+
+            #[doc = #doc_mut]
+            #[inline]
+            #struct_vis fn #col_mut(&mut self) -> &mut [T] {
+                &mut self.0[#idx]
+            }
+        }
+    });
+
+    let doc = format!(
+        "Struct-of-arrays container of [`{struct_id}`]s, \
+        with one `Vec` per field, all of the same length"
+    );
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        #struct_vis struct #soa_id<T>([::alloc::vec::Vec<T>; #n_fields]);
+
+        impl<T> #soa_id<T> {
+            #[inline]
+            #struct_vis const fn new() -> Self {
+                Self([const { ::alloc::vec::Vec::new() }; #n_fields])
+            }
+
+            #[inline]
+            #struct_vis fn with_capacity(capacity: usize) -> Self {
+                Self(core::array::from_fn(|_| ::alloc::vec::Vec::with_capacity(capacity)))
+            }
+
+            /// Number of structs in the container
+            #[inline]
+            #struct_vis fn len(&self) -> usize {
+                // all columns have the same length.
+                // Zero-field structs cannot be counted
+                match self.0.first() {
+                    Some(col) => col.len(),
+                    None => 0,
+                }
+            }
+
+            #[inline]
+            #struct_vis fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            #[inline]
+            #struct_vis fn reserve(&mut self, additional: usize) {
+                self.0.iter_mut().for_each(|col| col.reserve(additional));
+            }
+
+            #[inline]
+            #struct_vis fn clear(&mut self) {
+                self.0.iter_mut().for_each(::alloc::vec::Vec::clear);
+            }
+
+            #[inline]
+            #struct_vis fn push(&mut self, #struct_id([#(#a),*]): #struct_id<T>) {
+                let [#(#c),*] = &mut self.0;
+                #(#c.push(#a);)*
+            }
+
+            #[inline]
+            #struct_vis fn pop(&mut self) -> Option<#struct_id<T>> {
+                let [#(#c),*] = &mut self.0;
+                Some(#struct_id([#(#c.pop()?),*]))
+            }
+
+            #[inline]
+            #struct_vis fn get(&self, i: usize) -> Option<#struct_id<&T>> {
+                let [#(#c),*] = &self.0;
+                Some(#struct_id([#(#c.get(i)?),*]))
+            }
+
+            #[inline]
+            #struct_vis fn get_mut(&mut self, i: usize) -> Option<#struct_id<&mut T>> {
+                let [#(#c),*] = &mut self.0;
+                Some(#struct_id([#(#c.get_mut(i)?),*]))
+            }
+
+            /// Iterates over references to the fields of each struct, in order of insertion
+            #[inline]
+            #struct_vis fn iter(
+                &self,
+            ) -> impl DoubleEndedIterator<Item = #struct_id<&T>> + ExactSizeIterator {
+                let [#(#c),*] = &self.0;
+                (0..self.len()).map(move |i| #struct_id([#(&#c[i]),*]))
+            }
+
+            #(#cols)*
+        }
+
+        impl<T> Default for #soa_id<T> {
+            #[inline]
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<T> core::iter::FromIterator<#struct_id<T>> for #soa_id<T> {
+            #[inline]
+            fn from_iter<I: IntoIterator<Item = #struct_id<T>>>(iter: I) -> Self {
+                let mut res = Self::new();
+                res.extend(iter);
+                res
+            }
+        }
+
+        impl<T> core::iter::Extend<#struct_id<T>> for #soa_id<T> {
+            #[inline]
+            fn extend<I: IntoIterator<Item = #struct_id<T>>>(&mut self, iter: I) {
+                let iter = iter.into_iter();
+                self.reserve(iter.size_hint().0);
+                iter.for_each(|s| self.push(s));
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use quote::quote;
+
+use crate::GenericArrayStructParams;
+
+/// Outputs the token stream to append
+pub(crate) fn impl_transpose(params: &GenericArrayStructParams) -> proc_macro2::TokenStream {
+    let struct_id = params.struct_ident();
+    let len = params.len_expr();
+
+    // `Transpose` is blanket-implemented for all pairs of `GenericArrayStruct`s
+    quote! {
+        impl<T> ::generic_array_struct_rt::GenericArrayStruct for #struct_id<T> {
+            type Elem = T;
+            type WithElem<U> = #struct_id<U>;
+            type IntoElems = core::array::IntoIter<T, { #len }>;
+
+            #[inline]
+            fn from_fn<F: FnMut(usize) -> T>(f: F) -> Self {
+                Self(core::array::from_fn(f))
+            }
+
+            #[inline]
+            fn into_elems(self) -> Self::IntoElems {
+                IntoIterator::into_iter(self.0)
+            }
+
+            #[inline]
+            fn as_mut_slice(&mut self) -> &mut [T] {
+                &mut self.0
+            }
+
+            #[inline]
+            fn map<U, F: FnMut(T) -> U>(self, f: F) -> #struct_id<U> {
+                #struct_id(self.0.map(f))
+            }
+        }
+    }
+}
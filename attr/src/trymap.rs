@@ -1,42 +1,41 @@
-use quote::quote;
+use quote::{format_ident, quote};
 
 use crate::GenericArrayStructParams;
 
+/// Methods generated on the struct
+pub(crate) const METHODS: &[&str] = &[
+    "try_map_opt",
+    "try_map_res",
+    "try_map_ref_opt",
+    "try_map_ref_res",
+    "try_map_mut_opt",
+    "try_map_mut_res",
+    "try_for_each",
+    "try_update_in_place",
+    "transpose",
+];
+
+/// Methods generated on the struct with `trymap(alloc)`
+pub(crate) const ALLOC_METHODS: &[&str] = &["collect_errors"];
+
+/// Methods generated on the struct with `trymap(named)`
+pub(crate) const NAMED_METHODS: &[&str] = &["try_map_res_named", "try_map_with_field"];
+
+/// Methods generated on the struct with `trymap(generic)`
+pub(crate) const GENERIC_METHODS: &[&str] = &["try_map"];
+
 /// Outputs the token stream to append
 pub(crate) fn impl_trymap(params: &GenericArrayStructParams) -> proc_macro2::TokenStream {
     let struct_id = params.struct_ident();
 
-    quote! {
+    let mut res = quote! {
         impl<T> #struct_id <T> {
             #[inline]
             pub fn try_map_opt<B, F>(
                 self,
                 mut f: F,
             ) -> Option<#struct_id <B>> where F: FnMut(T) -> Option<B> {
-                let mut res: #struct_id <core::mem::MaybeUninit<B>>
-                    = #struct_id (core::array::from_fn(|_| core::mem::MaybeUninit::uninit()));
-                let written = self.0.into_iter().zip(res.0.iter_mut()).try_fold(
-                    0usize,
-                    |written, (val, rmut)| {
-                        rmut.write(f(val).ok_or(written)?);
-                        Ok(written + 1)
-                    }
-                );
-                match written {
-                    Ok(_) => Some(#struct_id (
-                        unsafe {
-                            core::mem::transmute_copy::<_, _>(
-                                &core::mem::ManuallyDrop::new(res.0)
-                            )
-                        }
-                    )),
-                    Err(written) => {
-                        res.0.iter_mut().take(written).for_each(
-                            |mu| unsafe { mu.assume_init_drop() }
-                        );
-                        None
-                    }
-                }
+                self.try_map_res(|val| f(val).ok_or(())).ok()
             }
 
             #[inline]
@@ -70,5 +69,171 @@ pub(crate) fn impl_trymap(params: &GenericArrayStructParams) -> proc_macro2::Tok
                 }
             }
         }
+    };
+
+    res.extend(quote! {
+        impl<T> #struct_id <T> {
+            /// [`Self::try_map_opt`] without consuming `self`
+            #[inline]
+            pub fn try_map_ref_opt<'a, B, F>(
+                &'a self,
+                f: F,
+            ) -> Option<#struct_id <B>> where F: FnMut(&'a T) -> Option<B> {
+                #struct_id(self.0.each_ref()).try_map_opt(f)
+            }
+
+            /// [`Self::try_map_res`] without consuming `self`
+            #[inline]
+            pub fn try_map_ref_res<'a, B, E, F>(
+                &'a self,
+                f: F,
+            ) -> Result<#struct_id <B>, E> where F: FnMut(&'a T) -> Result<B, E> {
+                #struct_id(self.0.each_ref()).try_map_res(f)
+            }
+
+            /// [`Self::try_map_opt`] on mutable references to the fields
+            #[inline]
+            pub fn try_map_mut_opt<'a, B, F>(
+                &'a mut self,
+                f: F,
+            ) -> Option<#struct_id <B>> where F: FnMut(&'a mut T) -> Option<B> {
+                #struct_id(self.0.each_mut()).try_map_opt(f)
+            }
+
+            /// [`Self::try_map_res`] on mutable references to the fields
+            #[inline]
+            pub fn try_map_mut_res<'a, B, E, F>(
+                &'a mut self,
+                f: F,
+            ) -> Result<#struct_id <B>, E> where F: FnMut(&'a mut T) -> Result<B, E> {
+                #struct_id(self.0.each_mut()).try_map_res(f)
+            }
+
+            /// Calls `f` on each field in array index order, stopping at the first error
+            #[inline]
+            pub fn try_for_each<E, F>(&self, f: F) -> Result<(), E> where F: FnMut(&T) -> Result<(), E> {
+                self.0.iter().try_for_each(f)
+            }
+
+            /// Calls `f` on each field in array index order, stopping at the first error.
+            ///
+            /// On error, all fields are rolled back to their values before this call.
+            #[inline]
+            pub fn try_update_in_place<E, F>(
+                &mut self,
+                f: F,
+            ) -> Result<(), E> where T: Clone, F: FnMut(&mut T) -> Result<(), E> {
+                let backup = self.0.clone();
+                self.0.iter_mut().try_for_each(f).inspect_err(|_| self.0 = backup)
+            }
+        }
+
+        impl<T> #struct_id <Option<T>> {
+            /// Returns `None` if any field is `None`
+            #[inline]
+            pub fn transpose(self) -> Option<#struct_id <T>> {
+                self.try_map_opt(core::convert::identity)
+            }
+        }
+
+        impl<T, E> #struct_id <Result<T, E>> {
+            /// Returns the error of the first field in array index order that is `Err`
+            #[inline]
+            pub fn transpose(self) -> Result<#struct_id <T>, E> {
+                self.try_map_res(core::convert::identity)
+            }
+        }
+    });
+
+    if params.args().trymap_opts.generic {
+        res.extend(quote! {
+            impl<T> #struct_id <T> {
+                /// Fallible map that short-circuits on the first field in array index order
+                /// that `f` fails for, for any [`generic_array_struct_rt::TryLike`] type
+                #[inline]
+                pub fn try_map<B, R, F>(
+                    self,
+                    mut f: F,
+                ) -> <R as ::generic_array_struct_rt::TryLike>::WithOutput<#struct_id <B>>
+                    where R: ::generic_array_struct_rt::TryLike<Output = B>, F: FnMut(T) -> R
+                {
+                    use ::generic_array_struct_rt::TryLike;
+
+                    match self.try_map_res(|val| match f(val).branch() {
+                        core::ops::ControlFlow::Continue(b) => Ok(b),
+                        core::ops::ControlFlow::Break(r) => Err(r),
+                    }) {
+                        Ok(res) => TryLike::from_output(res),
+                        Err(r) => TryLike::from_residual(r),
+                    }
+                }
+            }
+        });
     }
+
+    if params.args().trymap_opts.named {
+        let enum_id = params.field_enum_ident();
+        res.extend(quote! {
+            impl<T> #struct_id <T> {
+                /// [`Self::try_map_res`], but `f` also receives the field of the value
+                #[inline]
+                pub fn try_map_with_field<B, E, F>(
+                    self,
+                    mut f: F,
+                ) -> Result<#struct_id <B>, E> where F: FnMut(#enum_id, T) -> Result<B, E> {
+                    // try_map_res() calls f in array index order
+                    let mut idx = 0;
+                    self.try_map_res(|val| {
+                        let field = #enum_id::ALL[idx];
+                        idx += 1;
+                        f(field, val)
+                    })
+                }
+
+                /// [`Self::try_map_res`], but the error is annotated with the field that failed
+                #[inline]
+                pub fn try_map_res_named<B, E, F>(
+                    self,
+                    mut f: F,
+                ) -> Result<#struct_id <B>, ::generic_array_struct_rt::FieldError<#enum_id, E>>
+                    where F: FnMut(T) -> Result<B, E>
+                {
+                    self.try_map_with_field(|field, val| {
+                        f(val).map_err(|error| ::generic_array_struct_rt::FieldError { field, error })
+                    })
+                }
+            }
+        });
+    }
+
+    if params.args().trymap_opts.alloc {
+        let enum_id = params.field_enum_ident();
+        let n_fields = params.fields().len();
+        let idxs: Vec<_> = (0..n_fields).collect();
+        let a: Vec<_> = idxs.iter().map(|i| format_ident!("a{i}")).collect();
+        res.extend(quote! {
+            impl<T, E> #struct_id <Result<T, E>> {
+                /// Returns the errors of all fields that are `Err`, in array index order
+                #[inline]
+                pub fn collect_errors(
+                    self,
+                ) -> Result<#struct_id <T>, ::alloc::vec::Vec<(#enum_id, E)>> {
+                    let mut errs = ::alloc::vec::Vec::new();
+                    let Self([#(#a),*]) = self;
+                    let opts = #struct_id([#(
+                        match #a {
+                            Ok(v) => Some(v),
+                            Err(e) => {
+                                errs.push((#enum_id::ALL[#idxs], e));
+                                None
+                            }
+                        }
+                    ),*]);
+                    opts.transpose().ok_or(errs)
+                }
+            }
+        });
+    }
+
+    res
 }
@@ -1,4 +1,5 @@
-use syn::{Ident, Path, PathSegment};
+use proc_macro2::Span;
+use syn::{parse_quote, Ident, Path, PathSegment, VisRestricted, Visibility};
 
 /// Convert an Ident into a plain path with a single segment
 /// e.g.
@@ -18,3 +19,42 @@ pub(crate) fn path_from_ident(ident: Ident) -> Path {
         .collect(),
     }
 }
+
+/// Convert a Visibility of an item into the equivalent Visibility
+/// for an item in a child module. e.g.
+/// - private -> `pub(super)`
+/// - `pub(super)` -> `pub(in super::super)`
+/// - `pub(crate)` -> `pub(crate)`
+pub(crate) fn child_mod_vis(vis: &Visibility) -> Visibility {
+    match vis {
+        Visibility::Public(_) => vis.clone(),
+        Visibility::Inherited => parse_quote! { pub(super) },
+        Visibility::Restricted(VisRestricted { path, .. }) => {
+            let first = path.segments.first().map(|s| s.ident.to_string());
+            match first.as_deref() {
+                Some("self") => {
+                    let rest = path.segments.iter().skip(1);
+                    parse_quote! { pub(in super #(::#rest)*) }
+                }
+                Some("super") => parse_quote! { pub(in super::#path) },
+                _ => vis.clone(),
+            }
+        }
+    }
+}
+
+/// Smallest unsigned int type with at least `n_bits` bits
+/// e.g. `u8` for 3 bits.
+///
+/// `None` if more than 128 bits are required
+pub(crate) fn bits_ty(n_bits: usize, span: Span) -> Option<Ident> {
+    let ty = match n_bits {
+        0..=8 => "u8",
+        9..=16 => "u16",
+        17..=32 => "u32",
+        33..=64 => "u64",
+        65..=128 => "u128",
+        _ => return None,
+    };
+    Some(Ident::new(ty, span))
+}
@@ -2,9 +2,30 @@ use crate::GenericArrayStructParams;
 
 use quote::{format_ident, quote};
 
+/// Methods generated on the struct
+pub(crate) const METHODS: &[&str] = &[
+    "zip",
+    "const_zip",
+    "unzip",
+    "const_unzip",
+    "zip_iters",
+    "unzip_iter",
+    "zip_with",
+    "zip_ref",
+];
+
+/// Max arity of `zip(arity = ...)`, the max tuple size that std implements common traits for
+pub(crate) const MAX_ARITY: usize = 12;
+
+/// Names of the N-ary methods generated for `arity`
+pub(crate) fn nary_methods(arity: usize) -> impl Iterator<Item = String> {
+    (3..=arity)
+        .flat_map(|k| ["zip", "const_zip", "unzip", "const_unzip"].map(|m| format!("{m}{k}")))
+}
+
 /// Outputs the token stream to append
 pub(crate) fn impl_zip(params: &GenericArrayStructParams) -> proc_macro2::TokenStream {
-    let n_fields = params.fields_named().named.iter().count();
+    let n_fields = params.fields().len();
 
     let struct_id = params.struct_ident();
 
@@ -21,6 +42,14 @@ pub(crate) fn impl_zip(params: &GenericArrayStructParams) -> proc_macro2::TokenS
             )
         });
 
+    // used more than once per method below
+    let ts4: Vec<_> = (0..n_fields).map(|i| format_ident!("t{i}")).collect();
+    let us4: Vec<_> = (0..n_fields).map(|i| format_ident!("u{i}")).collect();
+    let cs: Vec<_> = (0..n_fields).map(|i| format_ident!("c{i}")).collect();
+    let idxs: Vec<_> = (0..n_fields).collect();
+
+    let nary = (3..=params.args().zip_arity).map(|k| impl_zip_nary(params, k));
+
     quote! {
         impl<T> #struct_id <T> {
             #[inline]
@@ -53,5 +82,113 @@ pub(crate) fn impl_zip(params: &GenericArrayStructParams) -> proc_macro2::TokenS
                 (#struct_id ([#(#ts3),*]), #struct_id ([#(#us3),*]))
             }
         }
+
+        impl<T> #struct_id <T> {
+            #[inline]
+            pub fn zip_with<U, __V, __F>(
+                self,
+                #struct_id ([#(#us4),*]): #struct_id <U>,
+                mut f: __F,
+            ) -> #struct_id <__V> where __F: FnMut(T, U) -> __V {
+                let Self([#(#ts4),*]) = self;
+                #struct_id ([#(f(#ts4, #us4)),*])
+            }
+
+            #[inline]
+            pub const fn zip_ref<'a, 'b, U>(
+                &'a self,
+                other: &'b #struct_id <U>,
+            ) -> #struct_id <(&'a T, &'b U)> {
+                #struct_id ([#((&self.0[#idxs], &other.0[#idxs])),*])
+            }
+        }
+
+        #(#nary)*
+
+        impl<__I: IntoIterator> #struct_id <__I> {
+            #[inline]
+            pub fn zip_iters(self) -> impl Iterator<Item = #struct_id <__I::Item>> {
+                let Self([#(#ts4),*]) = self;
+                #(let mut #ts4 = #ts4.into_iter();)*
+                core::iter::from_fn(move || Some(#struct_id ([#(#ts4.next()?),*])))
+            }
+        }
+
+        impl<__C> #struct_id <__C> {
+            #[inline]
+            pub fn unzip_iter<__T, __I>(iter: __I) -> Self
+                where __C: Default + Extend<__T>, __I: IntoIterator<Item = #struct_id <__T>>
+            {
+                #(let mut #cs = __C::default();)*
+                iter.into_iter().for_each(|#struct_id ([#(#ts4),*])| {
+                    #(#cs.extend(core::iter::once(#ts4));)*
+                });
+                Self([#(#cs),*])
+            }
+        }
+    }
+}
+
+/// `zip{k}`, `const_zip{k}`, `unzip{k}` and `const_unzip{k}`
+fn impl_zip_nary(params: &GenericArrayStructParams, k: usize) -> proc_macro2::TokenStream {
+    let n_fields = params.fields().len();
+    let struct_id = params.struct_ident();
+
+    let [zip, const_zip, unzip, const_unzip] =
+        ["zip", "const_zip", "unzip", "const_unzip"].map(|m| format_ident!("{m}{k}"));
+
+    // type params of the other operands, __U1..__U{k-1}
+    let us: Vec<_> = (1..k).map(|j| format_ident!("__U{j}")).collect();
+    // vals[j][i] is field i of operand j, where operand 0 is self
+    let vals: Vec<Vec<_>> = (0..k)
+        .map(|j| (0..n_fields).map(|i| format_ident!("v{j}_{i}")).collect())
+        .collect();
+    let self_vals = &vals[0];
+    let other_vals = &vals[1..];
+    let tuples: Vec<_> = (0..n_fields)
+        .map(|i| {
+            let elems = vals.iter().map(|v| &v[i]);
+            quote! { (#(#elems),*) }
+        })
+        .collect();
+
+    quote! {
+        impl<T> #struct_id <T> {
+            #[inline]
+            pub fn #zip<#(#us),*>(
+                self,
+                #(#struct_id ([#(#other_vals),*]): #struct_id <#us>),*
+            ) -> #struct_id <(T, #(#us),*)> {
+                let Self([#(#self_vals),*]) = self;
+                #struct_id ([#(#tuples),*])
+            }
+        }
+
+        impl<T: Copy> #struct_id <T> {
+            #[inline]
+            pub const fn #const_zip<#(#us: Copy),*>(
+                self,
+                #(#struct_id ([#(#other_vals),*]): #struct_id <#us>),*
+            ) -> #struct_id <(T, #(#us),*)> {
+                let Self([#(#self_vals),*]) = self;
+                #struct_id ([#(#tuples),*])
+            }
+        }
+
+        impl<T, #(#us),*> #struct_id <(T, #(#us),*)> {
+            #[inline]
+            pub fn #unzip(self) -> (#struct_id <T>, #(#struct_id <#us>),*) {
+                let Self([#(#tuples),*]) = self;
+                (#(#struct_id ([#(#vals),*])),*)
+            }
+        }
+
+        impl<T: Copy, #(#us: Copy),*> #struct_id <(T, #(#us),*)> {
+            #[inline]
+            pub const fn #const_unzip(self) -> (#struct_id <T>, #(#struct_id <#us>),*) {
+                let Self([#(#tuples),*]) = self;
+                (#(#struct_id ([#(#vals),*])),*)
+            }
+        }
     }
 }
@@ -1 +1,4 @@
+// for `trymap(alloc)`
+extern crate alloc;
+
 mod tests;
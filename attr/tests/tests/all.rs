@@ -0,0 +1,32 @@
+use generic_array_struct::generic_array_struct;
+
+#[generic_array_struct(all zip(arity = 3) trymap(named, alloc) builder(name = PointMaker) pub)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[test]
+fn all_with_options() {
+    // options of the args enabled by `all`
+    let p = Point([1u8, 2]);
+    assert_eq!(p.zip3(p, p), Point([(1, 1, 1), (2, 2, 2)]));
+    assert_eq!(
+        Point(["1", "a"])
+            .try_map_res_named(str::parse::<u8>)
+            .map_err(|e| e.field),
+        Err(PointField::Y)
+    );
+    assert_eq!(
+        Point([Err::<u8, _>(0), Err(1)]).collect_errors(),
+        Err(vec![(PointField::X, 0), (PointField::Y, 1)])
+    );
+    let built: Point<u8> = NewPointMaker::start().with_x(1).with_y(2).build();
+    assert_eq!(built, p);
+
+    // args enabled by `all` without options
+    assert_eq!(p.into_destr(), PointDestr { x: 1, y: 2 });
+    assert_eq!(p.try_map_opt(|v| v.checked_sub(1)), Some(Point([0, 1])));
+    assert_eq!(p.zip(p), Point([(1, 1), (2, 2)]));
+}
@@ -0,0 +1,76 @@
+use generic_array_struct::generic_array_struct;
+
+// would clash with each other's free `RGB_LEN` and `RGB_IDX_*` consts
+// if both were `consts(free)` in the same module
+
+#[generic_array_struct(builder consts(assoc))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb<T> {
+    pub r: T,
+    g: T,
+    pub(crate) b: T,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[generic_array_struct(builder consts(mod))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RGB<T> {
+    pub r: T,
+    g: T,
+    pub(crate) b: T,
+}
+
+#[generic_array_struct(consts(mod = idx))]
+pub struct Cartesian<T> {
+    pub x: T,
+    pub y: T,
+}
+
+mod nested {
+    use super::*;
+
+    #[generic_array_struct(consts(mod) pub(super))]
+    pub(super) struct Xy<T> {
+        pub(super) x: T,
+        y: T,
+    }
+
+    impl Xy<u8> {
+        pub(super) fn idx_y() -> usize {
+            xy_idx::Y
+        }
+    }
+}
+
+#[test]
+fn consts_assoc() {
+    assert_eq!(Rgb::<u8>::LEN, 3);
+    assert_eq!(Rgb::<u8>::IDX_B, 2);
+
+    let c: Rgb<u8> = NewRgbBuilder::start().with_r(1).with_g(2).with_b(3).build();
+    assert_eq!([*c.r(), *c.g(), *c.b()], [1, 2, 3]);
+}
+
+#[test]
+fn consts_mod() {
+    assert_eq!(rgb_idx::LEN, 3);
+    assert_eq!(rgb_idx::R, 0);
+    assert_eq!(rgb_idx::G, 1);
+    assert_eq!(rgb_idx::B, 2);
+    assert_eq!(RGB::<u8>::IDX_B, rgb_idx::B);
+
+    let mut c: RGB<u8> = NewRGBBuilder::start().with_r(1).with_g(2).with_b(3).build();
+    assert_eq!(c.set_g(4), 2);
+    assert_eq!(c.0, [1, 4, 3]);
+
+    assert_eq!(idx::LEN, 2);
+    assert_eq!(idx::Y, 1);
+    assert_eq!(*Cartesian([1, 2]).y(), 2);
+
+    assert_eq!(nested::xy_idx::LEN, 2);
+    assert_eq!(nested::xy_idx::X, 0);
+    assert_eq!(nested::Xy::<u8>::idx_y(), 1);
+    let xy = nested::Xy([1u8, 2]);
+    assert_eq!(*xy.x(), 1);
+    assert_eq!(xy.0, [1, 2]);
+}
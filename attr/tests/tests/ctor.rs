@@ -0,0 +1,82 @@
+use generic_array_struct::generic_array_struct;
+
+mod color {
+    use generic_array_struct::generic_array_struct;
+
+    /// Private array field, constructed only through the `ctor` arg's methods
+    #[generic_array_struct(ctor)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Rgb<T> {
+        pub r: T,
+        pub g: T,
+        pub b: T,
+    }
+}
+
+use color::{Rgb, RgbField};
+
+#[test]
+fn ctor_new_splat() {
+    const C: Rgb<u8> = Rgb::new(1, 2, 3);
+    assert_eq!([*C.r(), *C.g(), *C.b()], [1, 2, 3]);
+
+    const S: Rgb<u8> = Rgb::splat(7);
+    assert_eq!(S, Rgb::new(7, 7, 7));
+}
+
+#[test]
+fn ctor_from_fn() {
+    let mut calls = Vec::new();
+    let c = Rgb::from_fn(|field| {
+        calls.push(field);
+        field.idx() * 10
+    });
+    assert_eq!(c, Rgb::new(0, 10, 20));
+    assert_eq!(calls, RgbField::ALL);
+
+    let c: Result<Rgb<u8>, String> = Rgb::try_from_fn(|field| Ok(field.name().as_bytes()[0]));
+    assert_eq!(c, Ok(Rgb::new(b'r', b'g', b'b')));
+
+    let c: Result<Rgb<u8>, String> = Rgb::try_from_fn(|field| match field {
+        RgbField::B => Err(format!("no {field}")),
+        _ => Ok(0),
+    });
+    assert_eq!(c, Err("no b".to_owned()));
+}
+
+/// Stored in a different order from declaration, with a renamed field enum
+#[generic_array_struct(ctor field_enum(name = Channel) pub)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bgr<T> {
+    #[gas(index = 2)]
+    pub r: T,
+    pub g: T,
+    #[gas(index = 0)]
+    pub b: T,
+}
+
+#[test]
+fn field_enum_index_order() {
+    assert_eq!(Channel::ALL, [Channel::B, Channel::G, Channel::R]);
+    assert_eq!(Channel::R.idx(), 2);
+    assert_eq!(Channel::from_idx(0), Some(Channel::B));
+    assert_eq!(Channel::from_idx(3), None);
+    assert!(Channel::B < Channel::R);
+    assert_eq!(Channel::G.to_string(), "g");
+
+    // new() takes fields in declaration order
+    assert_eq!(Bgr::new(1, 2, 3).0, [3, 2, 1]);
+    assert_eq!(Bgr::from_fn(Channel::idx).0, [0, 1, 2]);
+}
+
+/// The field enum alone
+#[generic_array_struct(field_enum)]
+pub struct Cartesian<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[test]
+fn field_enum_only() {
+    assert_eq!(CartesianField::ALL.map(CartesianField::name), ["x", "y"]);
+}
@@ -0,0 +1,55 @@
+use generic_array_struct::ArrayFields;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ArrayFields)]
+#[fields(pub r, pub g, pub b)]
+#[gas(all)]
+#[repr(transparent)]
+pub struct Rgb<T>(pub [T; RGB_LEN]);
+
+#[test]
+fn derive_consts() {
+    assert_eq!(RGB_LEN, 3);
+    assert_eq!([RGB_IDX_R, RGB_IDX_G, RGB_IDX_B], [0, 1, 2]);
+    assert_eq!(Rgb::<u8>::IDX_B, 2);
+}
+
+#[test]
+fn derive_accessors() {
+    let mut c = Rgb([1u8, 2, 3]);
+    assert_eq!(*c.g(), 2);
+    *c.r_mut() = 4;
+    assert_eq!(c.set_b(5), 3);
+    assert_eq!(c.with_g(6).0, [4, 6, 5]);
+    assert_eq!(c.const_with_g(6).0, [4, 6, 5]);
+}
+
+#[test]
+fn derive_args() {
+    let c: Rgb<u8> = NewRgbBuilder::start().with_b(3).with_r(1).with_g(2).build();
+    assert_eq!(c.0, [1, 2, 3]);
+
+    let RgbDestr { r, g, b } = c.into_destr();
+    assert_eq!([r, g, b], [1, 2, 3]);
+    assert_eq!(Rgb::from_destr(RgbDestr { r, g, b }), c);
+
+    assert_eq!(c.try_map_opt(|x| x.checked_sub(1)), Some(Rgb([0, 1, 2])));
+    assert_eq!(c.zip(Rgb([4u8, 5, 6])).0, [(1, 4), (2, 5), (3, 6)]);
+}
+
+/// Field attributes, struct-level args and a literal array length
+#[derive(ArrayFields)]
+#[fields(
+    pub start,
+    #[gas(skip_mut, skip_set, skip_with, getter = "stop")]
+    pub end,
+)]
+#[gas(consts(assoc), getter = "get_{}")]
+pub struct Range<T>(pub [T; 2]);
+
+#[test]
+fn derive_field_attrs() {
+    let r = Range([1, 3]);
+    assert_eq!(*r.get_start(), 1);
+    assert_eq!(*r.stop(), 3);
+    assert_eq!(Range::<u8>::LEN, 2);
+}
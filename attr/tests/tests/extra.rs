@@ -0,0 +1,126 @@
+use core::marker::PhantomData;
+
+use generic_array_struct::generic_array_struct;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceId(pub String);
+
+/// Quotes of an instrument `M`, from a single source
+#[generic_array_struct(builder destr pub)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pricing<T, M> {
+    pub bid: T,
+    pub ask: T,
+
+    #[gas(extra)]
+    pub source: SourceId,
+
+    pub mid: T,
+
+    _m: PhantomData<M>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Btc;
+
+fn btc_pricing() -> Pricing<u32, Btc> {
+    Pricing([99, 101, 100], SourceId("ex".to_owned()), PhantomData)
+}
+
+#[test]
+fn extra_consts() {
+    assert_eq!(PRICING_LEN, 3);
+    assert_eq!(Pricing::<u32, Btc>::LEN, 3);
+    assert_eq!(Pricing::<u32, Btc>::IDX_MID, 2);
+}
+
+#[test]
+fn extra_accessors() {
+    let mut p = btc_pricing();
+    assert_eq!(*p.mid(), 100);
+    assert_eq!(p.source().0, "ex");
+
+    p.source_mut().0.push('2');
+    assert_eq!(p.source().0, "ex2");
+    let old = p.set_source(SourceId("other".to_owned()));
+    assert_eq!(old.0, "ex2");
+    assert_eq!(p.1 .0, "other");
+
+    let p = p.with_source(SourceId("ex".to_owned())).with_mid(1);
+    assert_eq!(p.0, [99, 101, 1]);
+    assert_eq!(p.source().0, "ex");
+}
+
+#[test]
+fn extra_builder() {
+    let p: Pricing<u32, Btc> = NewPricingBuilder::start()
+        .with_mid(100)
+        .with_ask(101)
+        .with_bid(99)
+        .build(SourceId("ex".to_owned()));
+    assert_eq!(p, btc_pricing());
+}
+
+#[test]
+fn extra_destr() {
+    let PricingDestr {
+        bid,
+        ask,
+        source,
+        mid,
+        _m,
+    } = btc_pricing().into_destr();
+    assert_eq!([bid, ask, mid], [99, 101, 100]);
+    assert_eq!(source.0, "ex");
+
+    let p = Pricing::from_destr(PricingDestr {
+        bid,
+        ask,
+        source,
+        mid,
+        _m,
+    });
+    assert_eq!(p, btc_pricing());
+}
+
+/// Struct-level bounds on the element type param and extra generics
+#[generic_array_struct(builder destr consts(assoc) pub)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tagged<'a, T: Copy + Default, const N: usize> {
+    pub x: T,
+    pub y: T,
+
+    #[gas(extra, getter = "tag_str", skip_set, skip_mut, skip_with)]
+    pub tag: &'a str,
+
+    #[gas(extra)]
+    pub ids: [u8; N],
+}
+
+#[test]
+fn extra_generics() {
+    const T: Tagged<'static, i8, 2> = NewTaggedBuilder::start()
+        .with_x(1)
+        .with_y(2)
+        .build("a", [3, 4])
+        .const_with_y(-2);
+    assert_eq!(T.tag_str(), &"a");
+    assert_eq!(T.ids(), &[3, 4]);
+    assert_eq!(T.0, [1, -2]);
+    assert_eq!(Tagged::from_destr(T.into_destr()), T);
+}
+
+/// `PhantomData` fields are automatically excluded from the array
+#[generic_array_struct(builder destr pub)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unit<T, U> {
+    pub val: T,
+    _u: PhantomData<U>,
+}
+
+#[test]
+fn phantom_only() {
+    const U: Unit<f64, Btc> = NewUnitBuilder::start().with_val(1.5).build();
+    assert_eq!(U.0, [1.5]);
+    assert_eq!(Unit::const_from_destr(U.const_into_destr()), U);
+}
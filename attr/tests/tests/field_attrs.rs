@@ -0,0 +1,63 @@
+use generic_array_struct::generic_array_struct;
+
+mod invariant {
+    use super::*;
+
+    /// A non-empty range
+    #[generic_array_struct(builder destr)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Range<T> {
+        #[gas(skip_mut, skip_set, skip_with)]
+        pub start: T,
+
+        #[gas(skip_mut, skip_set, skip_with, getter = "stop", vis = "pub(crate)")]
+        pub end: T,
+    }
+
+    impl Range<u8> {
+        pub fn new(start: u8, end: u8) -> Option<Self> {
+            (start < end).then_some(Self([start, end]))
+        }
+    }
+}
+
+use invariant::Range;
+
+#[test]
+fn field_attrs_skip_and_rename() {
+    let range = Range::new(1, 3).unwrap();
+    assert_eq!(*range.start(), 1);
+    assert_eq!(*range.stop(), 3);
+    assert!(Range::new(3, 1).is_none());
+
+    // builder and destr are unaffected by skips
+    let built = invariant::NewRangeBuilder::start()
+        .with_start(1)
+        .with_end(3)
+        .build();
+    assert_eq!(built, range);
+
+    let invariant::RangeDestr { start, end } = range.into_destr();
+    assert_eq!([start, end], [1, 3]);
+}
+
+#[test]
+fn field_attrs_custom_names() {
+    #[generic_array_struct]
+    pub struct Cartesian<T> {
+        #[gas(getter = "get_x", getter_mut = "x_ref_mut", setter = "put_x")]
+        x: T,
+
+        /// doc comments and other attrs are preserved
+        #[gas(with = "and_y", const_with = "const_and_y")]
+        y: T,
+    }
+
+    let mut pt = Cartesian([0u8; 2]).and_y(2).const_and_y(3);
+    assert_eq!(*pt.get_x(), 0);
+    *pt.x_ref_mut() = 1;
+    assert_eq!(pt.put_x(4), 1);
+    assert_eq!(*pt.get_x(), 4);
+    assert_eq!(pt.set_y(5), 3);
+    assert_eq!(pt.0, [4, 5]);
+}
@@ -0,0 +1,76 @@
+use generic_array_struct::generic_array_struct;
+
+#[generic_array_struct(fieldset pub)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fees<T> {
+    pub trading: T,
+    pub withdrawal: T,
+    pub deposit: T,
+}
+
+const TRADING_DEPOSIT: FeesFieldSet =
+    FeesFieldSet::from_fields(&[FeesField::Trading, FeesField::Deposit]);
+
+#[test]
+fn fieldset_ops() {
+    assert_eq!(TRADING_DEPOSIT.bits(), 0b101u8);
+    assert_eq!(TRADING_DEPOSIT.len(), 2);
+    assert!(TRADING_DEPOSIT.contains(FeesField::Deposit));
+    assert!(!TRADING_DEPOSIT.contains(FeesField::Withdrawal));
+    assert_eq!(!TRADING_DEPOSIT, FeesFieldSet::from(FeesField::Withdrawal));
+    assert_eq!(TRADING_DEPOSIT | !TRADING_DEPOSIT, FeesFieldSet::ALL);
+    assert_eq!(TRADING_DEPOSIT & !TRADING_DEPOSIT, FeesFieldSet::EMPTY);
+    assert_eq!(
+        TRADING_DEPOSIT - FeesField::Trading.into(),
+        FeesFieldSet::from_field(FeesField::Deposit)
+    );
+    assert_eq!(
+        TRADING_DEPOSIT ^ FeesFieldSet::ALL,
+        FeesFieldSet::from_field(FeesField::Withdrawal)
+    );
+    assert!(TRADING_DEPOSIT.is_subset(FeesFieldSet::ALL));
+    assert!(FeesFieldSet::ALL.is_superset(TRADING_DEPOSIT));
+    assert!(FeesFieldSet::ALL.is_all());
+    assert!(FeesFieldSet::default().is_empty());
+    assert_eq!(FeesFieldSet::from_bits(0xff), FeesFieldSet::ALL);
+
+    let mut set = FeesFieldSet::EMPTY;
+    assert!(set.insert(FeesField::Withdrawal));
+    assert!(!set.insert(FeesField::Withdrawal));
+    assert!(set.remove(FeesField::Withdrawal));
+    assert!(!set.remove(FeesField::Withdrawal));
+    set |= TRADING_DEPOSIT;
+    set -= FeesFieldSet::from_field(FeesField::Trading);
+    assert_eq!(set, FeesFieldSet::from_field(FeesField::Deposit));
+}
+
+#[test]
+fn fieldset_iter() {
+    let fields: Vec<_> = TRADING_DEPOSIT.into_iter().collect();
+    assert_eq!(fields, [FeesField::Trading, FeesField::Deposit]);
+    assert_eq!(FeesFieldSet::ALL.iter().len(), 3);
+    let set: FeesFieldSet = [FeesField::Deposit, FeesField::Trading]
+        .into_iter()
+        .collect();
+    assert_eq!(set, TRADING_DEPOSIT);
+    assert_eq!(format!("{set:?}"), "{Trading, Deposit}");
+}
+
+#[test]
+fn fieldset_diff_copy() {
+    let old = Fees([1, 2, 3]);
+    let mut new = old.clone();
+    new.set_trading(10);
+    new.set_deposit(30);
+    let dirty = old.diff(&new);
+    assert_eq!(dirty, TRADING_DEPOSIT);
+    assert!(old.diff(&old).is_empty());
+
+    let mut synced = old.clone();
+    synced.copy_fields_from(&new, dirty);
+    assert_eq!(synced, new);
+
+    let mut partial = old.clone();
+    partial.copy_fields_from(&new, FeesField::Deposit.into());
+    assert_eq!(partial, Fees([1, 2, 30]));
+}
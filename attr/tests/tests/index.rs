@@ -0,0 +1,68 @@
+use generic_array_struct::generic_array_struct;
+
+/// Stored as BGR in memory, declared as RGB
+#[generic_array_struct(all)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bgr<T> {
+    #[gas(index = 2)]
+    pub r: T,
+
+    pub g: T,
+
+    #[gas(index = 0)]
+    pub b: T,
+}
+
+/// Only some fields have explicit indices, the rest fill the remaining slots in order
+#[generic_array_struct(destr)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Partial<T> {
+    pub a: T,
+    pub b: T,
+    #[gas(index = 0)]
+    pub c: T,
+}
+
+#[test]
+fn index_consts() {
+    assert_eq!([BGR_IDX_B, BGR_IDX_G, BGR_IDX_R], [0, 1, 2]);
+    assert_eq!([Bgr::<u8>::IDX_B, Bgr::<u8>::IDX_R], [0, 2]);
+    assert_eq!([PARTIAL_IDX_C, PARTIAL_IDX_A, PARTIAL_IDX_B], [0, 1, 2]);
+}
+
+#[test]
+fn index_accessors_builder() {
+    let px: Bgr<u8> = NewBgrBuilder::start()
+        .with_r(255)
+        .with_g(128)
+        .with_b(0)
+        .build();
+    assert_eq!(px.0, [0, 128, 255]);
+    assert_eq!(*px.r(), 255);
+    assert_eq!(*px.b(), 0);
+    assert_eq!(
+        format!("{:?}", NewBgrBuilder::start().with_r(255u8)),
+        "BgrBuilder { r: 255, g: <unset>, b: <unset> }"
+    );
+}
+
+#[test]
+fn index_destr() {
+    let px = Bgr([0u8, 128, 255]);
+    let BgrDestr { r, g, b } = px.into_destr();
+    assert_eq!([r, g, b], [255, 128, 0]);
+    assert_eq!(Bgr::from_destr(BgrDestr { r, g, b }), px);
+    assert_eq!(Bgr::const_from_destr(px.const_into_destr()), px);
+
+    let p = Partial([3u8, 1, 2]);
+    let PartialDestr { a, b, c } = p.into_destr();
+    assert_eq!([a, b, c], [1, 2, 3]);
+}
+
+#[test]
+fn index_zip() {
+    let px = Bgr([0u8, 128, 255]);
+    let (l, r) = px.zip(Bgr([1u8, 2, 3])).unzip();
+    assert_eq!(l, px);
+    assert_eq!(*r.r(), 3);
+}
@@ -0,0 +1,107 @@
+use generic_array_struct::generic_array_struct;
+use generic_array_struct_rt::OverflowAt;
+
+#[generic_array_struct(int_ops(u64, u8) pub)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fees<T> {
+    pub trading: T,
+    pub withdrawal: T,
+}
+
+const BASE: Fees<u64> = Fees([100, 5]);
+const DOUBLED: Fees<u64> = match BASE.checked_mul(Fees([2, 2])) {
+    Ok(f) => f,
+    Err(_) => panic!("overflow"),
+};
+
+#[test]
+fn int_ops_checked() {
+    assert_eq!(DOUBLED, Fees([200, 10]));
+    assert_eq!(BASE.checked_add(BASE), Ok(DOUBLED));
+    assert_eq!(
+        BASE.checked_sub(DOUBLED),
+        Err(OverflowAt {
+            field: FeesField::Trading
+        })
+    );
+    assert_eq!(
+        Fees([1u8, 255]).checked_add(Fees([1, 1])),
+        Err(OverflowAt {
+            field: FeesField::Withdrawal
+        })
+    );
+    assert_eq!(
+        BASE.checked_sub(DOUBLED).unwrap_err().to_string(),
+        "arithmetic overflow at field `trading`"
+    );
+}
+
+#[test]
+fn int_ops_saturating_wrapping() {
+    assert_eq!(BASE.saturating_sub(DOUBLED), Fees([0, 0]));
+    assert_eq!(
+        Fees([250u8, 1]).saturating_add(Fees([10, 1])),
+        Fees([255, 2])
+    );
+    assert_eq!(Fees([250u8, 1]).wrapping_add(Fees([10, 1])), Fees([4, 2]));
+    assert_eq!(Fees([16u8, 3]).wrapping_mul(Fees([16, 3])), Fees([0, 9]));
+}
+
+#[test]
+fn int_ops_div_rem() {
+    assert_eq!(DOUBLED.checked_div(Fees([3, 2])), Ok(Fees([66, 5])));
+    assert_eq!(DOUBLED.checked_rem(Fees([3, 4])), Ok(Fees([2, 2])));
+    assert_eq!(
+        DOUBLED.checked_div(Fees([0, 0])),
+        Err(OverflowAt {
+            field: FeesField::Trading
+        })
+    );
+    assert_eq!(
+        DOUBLED.checked_rem(Fees([1, 0])),
+        Err(OverflowAt {
+            field: FeesField::Withdrawal
+        })
+    );
+    assert_eq!(
+        Pair([i8::MIN, 7]).checked_div(Pair([-1, 2])),
+        Err(OverflowAt {
+            field: PairField::A
+        })
+    );
+    assert_eq!(
+        Pair([i8::MIN, 7]).saturating_div(Pair([-1, 2])),
+        Pair([i8::MAX, 3])
+    );
+    assert_eq!(
+        Pair([i8::MIN, 7]).wrapping_div(Pair([-1, 2])),
+        Pair([i8::MIN, 3])
+    );
+    assert_eq!(Pair([i8::MIN, 7]).wrapping_rem(Pair([-1, 2])), Pair([0, 1]));
+}
+
+/// All primitive integer types if none are specified
+#[generic_array_struct(int_ops pub)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pair<T> {
+    pub a: T,
+    pub b: T,
+}
+
+#[test]
+fn int_ops_all_types() {
+    assert_eq!(
+        Pair([i8::MIN, 0]).saturating_sub(Pair([1, 1])),
+        Pair([i8::MIN, -1])
+    );
+    assert_eq!(
+        Pair([usize::MAX, 0]).wrapping_add(Pair([1, 1])),
+        Pair([0, 1])
+    );
+    assert_eq!(
+        Pair([1i128, i128::MAX]).checked_mul(Pair([2, 2])),
+        Err(OverflowAt {
+            field: PairField::B
+        })
+    );
+}
@@ -0,0 +1,93 @@
+use core::{
+    future::Future,
+    pin::{pin, Pin},
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+use std::{cell::RefCell, rc::Rc};
+
+use generic_array_struct::generic_array_struct;
+
+#[generic_array_struct(async pub)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fees<T> {
+    pub trading: T,
+    pub withdrawal: T,
+    pub deposit: T,
+}
+
+/// Minimal executor that busy-polls `fut` with a no-op waker
+fn block_on<F: Future>(fut: F) -> F::Output {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |_| RawWaker::new(core::ptr::null(), &VTABLE),
+        |_| {},
+        |_| {},
+        |_| {},
+    );
+    let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = pin!(fut);
+    loop {
+        if let Poll::Ready(o) = fut.as_mut().poll(&mut cx) {
+            return o;
+        }
+    }
+}
+
+/// Completes with `val` after being polled `delay` times,
+/// recording the order of completion in `log`
+struct Delayed<T> {
+    delay: usize,
+    val: Option<T>,
+    log: Rc<RefCell<Vec<usize>>>,
+    id: usize,
+}
+
+impl<T: Unpin> Future for Delayed<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        if self.delay == 0 {
+            let id = self.id;
+            self.log.borrow_mut().push(id);
+            Poll::Ready(self.val.take().expect("polled after completion"))
+        } else {
+            self.delay -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+fn delayed<T>(delays: [usize; 3], vals: [T; 3]) -> (Fees<Delayed<T>>, Rc<RefCell<Vec<usize>>>) {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut vals = vals.map(Some);
+    let futs = Fees(core::array::from_fn(|id| Delayed {
+        delay: delays[id],
+        val: vals[id].take(),
+        log: log.clone(),
+        id,
+    }));
+    (futs, log)
+}
+
+#[test]
+fn join_concurrent() {
+    let (futs, log) = delayed([2, 0, 1], [1u64, 2, 3]);
+    assert_eq!(block_on(futs.join()), Fees([1, 2, 3]));
+    // polled concurrently: completes in order of delay, not array index
+    assert_eq!(*log.borrow(), [1, 2, 0]);
+
+    let asyncs = Fees([1, 2, 3].map(|x| async move { x * 10 }));
+    assert_eq!(block_on(asyncs.join()), Fees([10, 20, 30]));
+}
+
+#[test]
+fn try_join_ok_err() {
+    let (futs, _) = delayed([1, 0, 2], [Ok::<_, &str>(1), Ok(2), Ok(3)]);
+    assert_eq!(block_on(futs.try_join()), Ok(Fees([1, 2, 3])));
+
+    let (futs, log) = delayed([2, 0, 1], [Ok(1), Ok(2), Err("deposit")]);
+    assert_eq!(block_on(futs.try_join()), Err("deposit"));
+    // completes early without waiting for trading
+    assert_eq!(*log.borrow(), [1, 2]);
+}
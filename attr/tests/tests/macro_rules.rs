@@ -0,0 +1,59 @@
+use generic_array_struct::generic_array_struct;
+
+mod color {
+    use generic_array_struct::generic_array_struct;
+
+    /// Private array field, so `Rgb([..])` cannot be used outside this module
+    #[generic_array_struct(macro_rules(path = crate::tests::macro_rules::color::Rgb))]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Rgb<T> {
+        pub r: T,
+        pub g: T,
+        pub b: T,
+    }
+}
+
+use color::{rgb, Rgb};
+
+#[test]
+fn macro_any_order() {
+    let c = rgb! { g: 2u8, b: 3, r: 1 };
+    assert_eq!([*c.r(), *c.g(), *c.b()], [1, 2, 3]);
+    assert_eq!(c, rgb! { r: 1, g: 2, b: 3, });
+}
+
+#[test]
+fn macro_const() {
+    const C: Rgb<u8> = rgb! { b: 3, g: 2, r: 1 };
+    const D: Rgb<u8> = rgb! { g: 5, ..C };
+    assert_eq!(C, rgb! { r: 1, g: 2, b: 3 });
+    assert_eq!([*D.r(), *D.g(), *D.b()], [1, 5, 3]);
+}
+
+#[test]
+fn macro_base() {
+    let r = String::from("r");
+    let base = rgb! { r: String::new(), g: "g".to_owned(), b: "b".to_owned() };
+    // field values may refer to variables with the same names as the fields
+    let c = rgb! { r: r, ..base };
+    assert_eq!(c.r(), "r");
+    assert_eq!(c.b(), "b");
+    assert_eq!(rgb! { ..c.clone() }, c);
+}
+
+/// Stored in a different order from declaration
+#[generic_array_struct(macro_rules(name = bgr) pub)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bgr<T> {
+    #[gas(index = 2)]
+    pub r: T,
+    pub g: T,
+    #[gas(index = 0)]
+    pub b: T,
+}
+
+#[test]
+fn macro_index() {
+    assert_eq!(bgr! { r: 1, g: 2, b: 3 }.0, [3, 2, 1]);
+    assert_eq!(bgr! { b: 4, ..Bgr([3, 2, 1]) }.0, [4, 2, 1]);
+}
@@ -1 +1,19 @@
+mod all;
 mod basic;
+mod consts;
+mod ctor;
+mod derive;
+mod extra;
+mod field_attrs;
+mod fieldset;
+mod index;
+mod int_ops;
+mod join;
+mod macro_rules;
+mod naming;
+mod patch;
+mod reduce;
+mod soa;
+mod transpose;
+mod trymap;
+mod zip;
@@ -0,0 +1,41 @@
+use generic_array_struct::generic_array_struct;
+
+#[generic_array_struct(
+    builder(name = RgbMaker, new = StartRgbMaker)
+    destr(name = RgbFields)
+    consts(prefix = COLOR)
+    getter = "get_{}"
+    getter_mut = "{}_ref_mut"
+    setter = "put_{}"
+    with = "and_{}"
+    const_with = "const_and_{}"
+    pub
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb<T> {
+    pub r: T,
+    pub g: T,
+
+    #[gas(getter = "blue")]
+    pub b: T,
+}
+
+#[test]
+fn naming_overrides() {
+    assert_eq!(COLOR_LEN, 3);
+    assert_eq!(COLOR_IDX_B, 2);
+
+    let mut c: Rgb<u8> = StartRgbMaker::start().and_r(1).and_g(2).and_b(3).build();
+    assert_eq!(*c.get_r(), 1);
+    assert_eq!(*c.blue(), 3);
+
+    *c.g_ref_mut() = 4;
+    assert_eq!(c.put_r(5), 1);
+    let c = c.and_b(6).const_and_g(7);
+    assert_eq!(c.0, [5, 7, 6]);
+
+    let RgbFields { r, g, b } = c.into_destr();
+    assert_eq!([r, g, b], [5, 7, 6]);
+
+    let _: RgbMaker<u8, false, false, false> = StartRgbMaker::start();
+}
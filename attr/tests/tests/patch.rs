@@ -0,0 +1,52 @@
+use generic_array_struct::generic_array_struct;
+
+#[generic_array_struct(patch pub)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile<T> {
+    pub name: T,
+    pub email: T,
+    pub bio: T,
+}
+
+const EMPTY: Profile<Option<String>> = Profile([None, None, None]);
+const _: () = assert!(EMPTY.is_empty());
+
+fn profile(name: &str, email: &str, bio: &str) -> Profile<String> {
+    Profile([name, email, bio].map(str::to_owned))
+}
+
+#[test]
+fn patch_from_apply_to() {
+    let old = profile("a", "a@x.com", "hi");
+    let new = profile("a", "a@y.com", "hello");
+    let patch = Profile::patch_from(&old, &new);
+    assert_eq!(
+        patch,
+        Profile([None, Some("a@y.com".to_owned()), Some("hello".to_owned())])
+    );
+    assert!(!patch.is_empty());
+    assert!(Profile::patch_from(&old, &old).is_empty());
+
+    let mut target = old.clone();
+    patch.apply_to(&mut target);
+    assert_eq!(target, new);
+
+    let mut untouched = old.clone();
+    EMPTY.apply_to(&mut untouched);
+    assert_eq!(untouched, old);
+}
+
+#[test]
+fn patch_merge() {
+    let first = Profile([Some(1), Some(2), None]);
+    let second = Profile([None, Some(20), Some(30)]);
+    let merged = first.clone().merge(second.clone());
+    assert_eq!(merged, Profile([Some(1), Some(20), Some(30)]));
+
+    let mut sequential = Profile([0, 0, 0]);
+    first.apply_to(&mut sequential);
+    second.apply_to(&mut sequential);
+    let mut at_once = Profile([0, 0, 0]);
+    merged.apply_to(&mut at_once);
+    assert_eq!(sequential, at_once);
+}
@@ -0,0 +1,64 @@
+use generic_array_struct::generic_array_struct;
+
+#[generic_array_struct(reduce pub)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fees<T> {
+    pub trading: T,
+    pub withdrawal: T,
+    pub deposit: T,
+}
+
+#[test]
+fn reduce_sum_product() {
+    let fees = vec![
+        Fees([1u64, 2, 3]),
+        Fees([10, 20, 30]),
+        Fees([100, 200, 300]),
+    ];
+    assert_eq!(fees.iter().sum::<Fees<u64>>(), Fees([111, 222, 333]));
+    assert_eq!(
+        fees.iter().product::<Fees<u64>>(),
+        Fees([1000, 8000, 27000])
+    );
+    assert_eq!(fees.into_iter().sum::<Fees<u64>>(), Fees([111, 222, 333]));
+
+    let empty: [Fees<i32>; 0] = [];
+    assert_eq!(empty.into_iter().sum::<Fees<i32>>(), Fees([0, 0, 0]));
+    assert_eq!(empty.into_iter().product::<Fees<i32>>(), Fees([1, 1, 1]));
+}
+
+#[test]
+fn reduce_fields() {
+    let fees = Fees([3u64, 1, 3]);
+    assert_eq!(fees.sum_fields(), 7);
+    assert_eq!(fees.product_fields(), 9);
+    assert_eq!(
+        fees.fold_fields(String::new(), |acc, field, val| format!(
+            "{acc}{field}={val};"
+        )),
+        "trading=3;withdrawal=1;deposit=3;"
+    );
+}
+
+#[test]
+fn reduce_min_max() {
+    let fees = Fees([3u64, 1, 3]);
+    assert_eq!(fees.min_field(), (FeesField::Withdrawal, &1));
+    // ties resolve to the first field in array index order
+    assert_eq!(fees.max_field(), (FeesField::Trading, &3));
+    assert_eq!(fees.argmin(), FeesField::Withdrawal);
+    assert_eq!(fees.argmax(), FeesField::Trading);
+    assert_eq!(Fees([0, 0, 0]).argmin(), FeesField::Trading);
+}
+
+#[test]
+fn reduce_min_max_float() {
+    let fees = Fees([f64::NAN, 0.5, -1.0]);
+    assert_eq!(fees.argmin(), FeesField::Deposit);
+    assert_eq!(fees.argmax(), FeesField::Withdrawal);
+    assert_eq!(Fees([1.0, f64::NAN, 1.0]).argmax(), FeesField::Trading);
+
+    let all_nan = Fees([f64::NAN; 3]);
+    assert_eq!(all_nan.argmin(), FeesField::Trading);
+    assert!(all_nan.max_field().1.is_nan());
+}
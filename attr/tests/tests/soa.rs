@@ -0,0 +1,60 @@
+use generic_array_struct::generic_array_struct;
+
+#[generic_array_struct(soa pub)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rgb<T> {
+    pub r: T,
+    pub g: T,
+    pub b: T,
+}
+
+#[generic_array_struct(soa(name = Samples) pub)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample<T> {
+    pub level: T,
+}
+
+#[test]
+fn soa_push_get() {
+    const EMPTY: RgbVec<f32> = RgbVec::new();
+    assert!(EMPTY.is_empty());
+
+    let mut v = RgbVec::with_capacity(2);
+    v.push(Rgb([0.1f32, 0.2, 0.3]));
+    v.push(Rgb([1.1, 1.2, 1.3]));
+    assert_eq!(v.len(), 2);
+    assert_eq!(v.get(1), Some(Rgb([&1.1, &1.2, &1.3])));
+    assert_eq!(v.get(2), None);
+
+    **v.get_mut(0).unwrap().g_mut() = 0.0;
+    assert_eq!(v.r_col(), [0.1, 1.1]);
+    assert_eq!(v.g_col(), [0.0, 1.2]);
+    v.b_col_mut().iter_mut().for_each(|b| *b *= 2.0);
+    assert_eq!(v.b_col(), [0.6, 2.6]);
+
+    assert_eq!(v.pop(), Some(Rgb([1.1, 1.2, 2.6])));
+    v.clear();
+    assert_eq!(v.pop(), None);
+    assert_eq!(v, RgbVec::default());
+}
+
+#[test]
+fn soa_iter() {
+    let v: RgbVec<u8> = (0..3).map(|i| Rgb([i, i * 2, i * 3])).collect();
+    assert_eq!(v.g_col(), [0, 2, 4]);
+    assert_eq!(v.iter().len(), 3);
+    assert_eq!(
+        v.iter()
+            .rev()
+            .map(|Rgb([r, g, b])| r + g + b)
+            .collect::<Vec<_>>(),
+        [12, 6, 0]
+    );
+
+    let mut v = v;
+    v.extend([Rgb([9, 9, 9])]);
+    assert_eq!(v.iter().last(), Some(Rgb([&9, &9, &9])));
+
+    let samples: Samples<u8> = [Sample([1]), Sample([2])].into_iter().collect();
+    assert_eq!(samples.level_col(), [1, 2]);
+}
@@ -0,0 +1,81 @@
+use generic_array_struct::generic_array_struct;
+use generic_array_struct_rt::{GenericArrayStruct, Transpose};
+
+#[generic_array_struct(transpose pub)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rgb<T> {
+    pub r: T,
+    pub g: T,
+    pub b: T,
+}
+
+#[generic_array_struct(transpose pub)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Xy<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[test]
+fn transpose_rect() {
+    let per_axis: Xy<Rgb<f32>> = Xy([Rgb([0.1, 0.2, 0.3]), Rgb([1.1, 1.2, 1.3])]);
+    let per_channel: Rgb<Xy<f32>> = per_axis.transpose();
+    assert_eq!(
+        per_channel,
+        Rgb([Xy([0.1, 1.1]), Xy([0.2, 1.2]), Xy([0.3, 1.3])])
+    );
+    assert_eq!(per_channel.g().y(), per_axis.y().g());
+    assert_eq!(per_channel.transpose(), per_axis);
+}
+
+#[test]
+fn transpose_square() {
+    let m = Rgb([
+        Rgb([String::from("rr"), String::from("rg"), String::from("rb")]),
+        Rgb([String::from("gr"), String::from("gg"), String::from("gb")]),
+        Rgb([String::from("br"), String::from("bg"), String::from("bb")]),
+    ]);
+    let t = m.clone().transpose();
+    assert_eq!(
+        t.map(|row| row.map(|s| s.chars().rev().collect::<String>())),
+        m
+    );
+}
+
+#[test]
+fn generic_array_struct_trait() {
+    let rgb = Rgb::from_fn(|i| i * 10);
+    assert_eq!(rgb, Rgb([0, 10, 20]));
+    assert_eq!(rgb.into_elems().collect::<Vec<_>>(), [0, 10, 20]);
+
+    let mut rgb = rgb;
+    rgb.as_mut_slice()[1] = 11;
+    assert_eq!(rgb.map(|x| x + 1), Rgb([1, 12, 21]));
+}
+
+#[generic_array_struct(transpose ctor pub)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Uv<T> {
+    pub u: T,
+    pub v: T,
+}
+
+#[test]
+fn ctor_from_fn_shadows_trait() {
+    assert_eq!(Uv::from_fn(|f| f == UvField::V), Uv([false, true]));
+    assert_eq!(<Uv<_> as GenericArrayStruct>::from_fn(|i| i), Uv([0, 1]));
+}
+
+#[generic_array_struct(transpose consts(mod) pub)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Kitchen<T> {
+    pub fridge: T,
+    pub oven: T,
+}
+
+#[test]
+fn transpose_consts_mod() {
+    let k = Kitchen([Xy([1, 2]), Xy([3, 4])]);
+    assert_eq!(k.transpose(), Xy([Kitchen([1, 3]), Kitchen([2, 4])]));
+    assert_eq!(kitchen_idx::LEN, 2);
+}
@@ -0,0 +1,244 @@
+use core::{ops::ControlFlow, task::Poll};
+use std::rc::Rc;
+
+use generic_array_struct::generic_array_struct;
+
+use generic_array_struct_rt::{FieldError, TryLike};
+
+#[generic_array_struct(trymap(alloc, named, generic) pub)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fees<T> {
+    pub trading: T,
+    pub withdrawal: T,
+    pub deposit: T,
+}
+
+#[test]
+fn trymap_transpose() {
+    assert_eq!(
+        Fees([Some(1), Some(2), Some(3)]).transpose(),
+        Some(Fees([1, 2, 3]))
+    );
+    assert_eq!(Fees([Some(1), None, Some(3)]).transpose(), None);
+
+    let ok: Fees<Result<u8, &str>> = Fees([Ok(1), Ok(2), Ok(3)]);
+    assert_eq!(ok.transpose(), Ok(Fees([1, 2, 3])));
+    let err: Fees<Result<u8, &str>> = Fees([Ok(1), Err("withdrawal"), Err("deposit")]);
+    assert_eq!(err.transpose(), Err("withdrawal"));
+}
+
+#[test]
+fn trymap_collect_errors() {
+    let ok: Fees<Result<String, u8>> =
+        Fees([Ok("a".to_owned()), Ok("b".to_owned()), Ok("c".to_owned())]);
+    assert_eq!(
+        ok.collect_errors(),
+        Ok(Fees(["a", "b", "c"].map(str::to_owned)))
+    );
+
+    let err: Fees<Result<String, u8>> = Fees([Err(0), Ok("b".to_owned()), Err(2)]);
+    assert_eq!(
+        err.collect_errors(),
+        Err(vec![(FeesField::Trading, 0), (FeesField::Deposit, 2)])
+    );
+}
+
+#[test]
+fn trymap_named() {
+    let fees = Fees(["1", "x", "y"]);
+    let err = fees
+        .clone()
+        .try_map_res_named(str::parse::<u8>)
+        .unwrap_err();
+    assert_eq!(err.field, FeesField::Withdrawal);
+    assert_eq!(err.field.name(), "withdrawal");
+    assert_eq!(err.field.idx(), 1);
+    assert_eq!(
+        err.to_string(),
+        "error at field `withdrawal`: invalid digit found in string"
+    );
+    assert!(std::error::Error::source(&err).is_some());
+
+    assert_eq!(
+        Fees(["1", "2", "3"]).try_map_res_named(str::parse::<u8>),
+        Ok(Fees([1, 2, 3]))
+    );
+    assert_eq!(
+        Fees([1u8, 0, 0]).try_map_res_named(|x| x.checked_sub(1).ok_or(x)),
+        Err(FieldError {
+            field: FeesField::Withdrawal,
+            error: 0
+        })
+    );
+}
+
+#[test]
+fn trymap_with_field() {
+    let fees = Fees([1u8, 2, 3]);
+    assert_eq!(
+        fees.clone()
+            .try_map_with_field(|field, x| Ok::<_, ()>(format!("{field}={x}"))),
+        Ok(Fees(
+            ["trading=1", "withdrawal=2", "deposit=3"].map(str::to_owned)
+        ))
+    );
+    assert_eq!(
+        fees.try_map_with_field(|field, x| match field {
+            FeesField::Deposit => Err(x),
+            _ => Ok(x),
+        }),
+        Err(3)
+    );
+}
+
+#[test]
+fn trymap_by_ref() {
+    let fees = Fees(["1".to_owned(), "2".to_owned(), "x".to_owned()]);
+    assert_eq!(fees.try_map_ref_opt(|s| s.parse::<u8>().ok()), None);
+    assert_eq!(
+        fees.try_map_ref_res(|s| s.parse::<u8>().map_err(|_| s.as_str())),
+        Err("x")
+    );
+    assert_eq!(
+        fees.try_map_ref_opt(|s| s.chars().next()),
+        Some(Fees(['1', '2', 'x']))
+    );
+    assert_eq!(
+        fees.try_for_each(|s| s.parse::<u8>().map(|_| ()).map_err(|_| s.len())),
+        Err(1)
+    );
+
+    let mut fees = Fees([1u8, 2, 3]);
+    let doubled = fees.try_map_mut_opt(|x| {
+        *x *= 2;
+        Some(*x)
+    });
+    assert_eq!(doubled, Some(Fees([2, 4, 6])));
+    assert_eq!(fees, Fees([2, 4, 6]));
+    assert_eq!(fees.try_map_mut_res(|x| x.checked_sub(4).ok_or(*x)), Err(2));
+}
+
+#[test]
+fn trymap_update_in_place() {
+    let mut fees = Fees([1u8, 2, 255]);
+    assert_eq!(
+        fees.try_update_in_place(|x| x.checked_add(1).map(|y| *x = y).ok_or(*x)),
+        Err(255)
+    );
+    // fields updated before the failure are rolled back
+    assert_eq!(fees, Fees([1, 2, 255]));
+
+    assert_eq!(
+        fees.try_update_in_place(|x| x.checked_sub(1).map(|y| *x = y).ok_or(*x)),
+        Ok(())
+    );
+    assert_eq!(fees, Fees([0, 1, 254]));
+}
+
+/// User-defined result-like type
+#[derive(Debug, PartialEq, Eq)]
+enum ProgramResult<T> {
+    Ok(T),
+    Err(u32),
+}
+
+impl<T> TryLike for ProgramResult<T> {
+    type Output = T;
+    type Residual = u32;
+    type WithOutput<O> = ProgramResult<O>;
+
+    fn branch(self) -> ControlFlow<u32, T> {
+        match self {
+            Self::Ok(t) => ControlFlow::Continue(t),
+            Self::Err(code) => ControlFlow::Break(code),
+        }
+    }
+
+    fn from_output(output: T) -> Self {
+        Self::Ok(output)
+    }
+
+    fn from_residual(code: u32) -> Self {
+        Self::Err(code)
+    }
+}
+
+#[test]
+fn trymap_generic() {
+    let fees = Fees([1u8, 2, 3]);
+
+    assert_eq!(
+        fees.clone().try_map(|x| x.checked_sub(1)),
+        Some(Fees([0, 1, 2]))
+    );
+    assert_eq!(fees.clone().try_map(|x| x.checked_sub(2)), None);
+    assert_eq!(fees.clone().try_map(|x| x.checked_sub(3).ok_or(x)), Err(1));
+    assert_eq!(
+        fees.clone().try_map(|x| match x {
+            2 => ControlFlow::Break("two"),
+            x => ControlFlow::Continue(x),
+        }),
+        ControlFlow::Break("two")
+    );
+    assert_eq!(
+        fees.clone().try_map(|x| match x {
+            2 => Poll::Pending,
+            x => Poll::Ready(Ok::<_, ()>(x)),
+        }),
+        Ok(Fees([Poll::Ready(1), Poll::Pending, Poll::Ready(3)]))
+    );
+    assert_eq!(
+        fees.clone()
+            .try_map(|x| Poll::Ready(if x == 3 { Err(x) } else { Ok(x) })),
+        Err(3)
+    );
+    assert_eq!(
+        fees.clone().try_map(|x| ProgramResult::Ok(u32::from(x))),
+        ProgramResult::Ok(Fees([1, 2, 3]))
+    );
+    assert_eq!(
+        fees.try_map(|x| if x > 1 {
+            ProgramResult::Err(u32::from(x))
+        } else {
+            ProgramResult::Ok(x)
+        }),
+        ProgramResult::Err(2)
+    );
+}
+
+#[test]
+fn trymap_generic_drops_written() {
+    let rc = Rc::new(());
+    let fees = Fees([rc.clone(), rc.clone(), rc.clone()]);
+    assert_eq!(Rc::strong_count(&rc), 4);
+
+    // fails at the second field: the first, already mapped field, the failing one
+    // and the unmapped last one must each be dropped exactly once
+    let mut calls = 0;
+    let res = fees.try_map(|v| {
+        calls += 1;
+        (calls != 2).then_some(v)
+    });
+    assert!(res.is_none());
+    assert_eq!(calls, 2);
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    let fees = Fees([rc.clone(), rc.clone(), rc.clone()]);
+    let mapped = fees.try_map(Some).unwrap();
+    assert_eq!(Rc::strong_count(&rc), 4);
+    drop(mapped);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn trymap_opt_drops_written() {
+    let rc = Rc::new(());
+    let fees = Fees([rc.clone(), rc.clone(), rc.clone()]);
+    let mut calls = 0;
+    let res = fees.try_map_opt(|v| {
+        calls += 1;
+        (calls != 3).then_some(v)
+    });
+    assert!(res.is_none());
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
@@ -0,0 +1,71 @@
+use generic_array_struct::generic_array_struct;
+
+#[generic_array_struct(zip(arity = 4) pub)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cartesian<T> {
+    pub x: T,
+    pub y: T,
+}
+
+const A: Cartesian<u8> = Cartesian([1, 2]);
+const B: Cartesian<f32> = Cartesian([0.5, 1.5]);
+const C: Cartesian<char> = Cartesian(['x', 'y']);
+const D: Cartesian<bool> = Cartesian([true, false]);
+
+const ABC: Cartesian<(u8, f32, char)> = A.const_zip3(B, C);
+const ABC_UNZ: (Cartesian<u8>, Cartesian<f32>, Cartesian<char>) = ABC.const_unzip3();
+
+#[test]
+fn zip_with_ref() {
+    assert_eq!(
+        A.zip_with(B, |a, b| f32::from(a) * b),
+        Cartesian([0.5, 3.0])
+    );
+    assert_eq!(
+        Cartesian([String::from("a"), String::from("b")]).zip_with(A, |s, n| s.repeat(n.into())),
+        Cartesian([String::from("a"), String::from("bb")])
+    );
+
+    const AB_REF: Cartesian<(&u8, &f32)> = A.zip_ref(&B);
+    assert_eq!(AB_REF, Cartesian([(&1, &0.5), (&2, &1.5)]));
+}
+
+#[test]
+fn zip_nary() {
+    assert_eq!(ABC, Cartesian([(1, 0.5, 'x'), (2, 1.5, 'y')]));
+    assert_eq!(ABC, A.zip3(B, C));
+    assert_eq!(ABC_UNZ, (A, B, C));
+    assert_eq!(ABC_UNZ, ABC.unzip3());
+
+    let abcd = A.zip4(B, C, D);
+    assert_eq!(abcd, Cartesian([(1, 0.5, 'x', true), (2, 1.5, 'y', false)]));
+    assert_eq!(abcd.unzip4(), (A, B, C, D));
+    assert_eq!(A.const_zip4(B, C, D).const_unzip4(), (A, B, C, D));
+
+    // 2-way methods are still generated
+    assert_eq!(A.zip(B).unzip(), (A, B));
+}
+
+// struct names that match generic params of the generated methods
+#[generic_array_struct(zip(arity = 3) pub)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct V<T> {
+    pub a: T,
+    pub b: T,
+}
+
+#[generic_array_struct(zip(arity = 3) pub)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct U1<T> {
+    pub a: T,
+}
+
+#[test]
+fn zip_struct_name_not_shadowed() {
+    assert_eq!(V([1, 2]).zip_with(V([3, 4]), |a, b| a * b), V([3, 8]));
+    assert_eq!(
+        V::<Vec<u8>>::unzip_iter(V([vec![1, 2], vec![3, 4]]).zip_iters()),
+        V([vec![1, 2], vec![3, 4]])
+    );
+    assert_eq!(U1([1]).zip3(U1([2]), U1([3])), U1([(1, 2, 3)]));
+}
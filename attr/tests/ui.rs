@@ -0,0 +1,5 @@
+/// Checks the exact messages and spans of compile errors
+#[test]
+fn ui() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,9 @@
+use generic_array_struct::generic_array_struct;
+
+#[generic_array_struct(builder(name = Cartesian))]
+pub struct Cartesian<T> {
+    pub x: T,
+    pub y: T,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use generic_array_struct::generic_array_struct;
+
+#[generic_array_struct(ctor)]
+pub struct Cartesian<T> {
+    pub x: T,
+    pub new: T,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use generic_array_struct::generic_array_struct;
+
+#[generic_array_struct(field_enum)]
+pub struct Cartesian<T> {
+    pub x1: T,
+    pub x_1: T,
+}
+
+fn main() {}
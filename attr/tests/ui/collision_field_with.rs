@@ -0,0 +1,9 @@
+use generic_array_struct::generic_array_struct;
+
+#[generic_array_struct]
+pub struct Cartesian<T> {
+    pub x: T,
+    pub with_x: T,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use generic_array_struct::generic_array_struct;
+
+#[generic_array_struct(field_enum)]
+pub struct Cartesian<T> {
+    pub a_l_l: T,
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use generic_array_struct::generic_array_struct;
+
+#[generic_array_struct(builder builder)]
+pub struct DupFlag<T> {
+    pub x: T,
+}
+
+#[generic_array_struct(builder(name = A, name = B))]
+pub struct DupName<T> {
+    pub x: T,
+}
+
+#[generic_array_struct(consts(mod = a, mod = b))]
+pub struct DupConstsMod<T> {
+    pub x: T,
+}
+
+#[generic_array_struct(consts(free, assoc))]
+pub struct DupConstsMode<T> {
+    pub x: T,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use generic_array_struct::generic_array_struct;
+
+#[generic_array_struct(ctor reduce)]
+pub struct Bounded<T: Copy> {
+    pub x: T,
+    pub y: T,
+}
+
+fn main() {}
@@ -3,7 +3,7 @@
 use generic_array_struct::generic_array_struct;
 
 /// A `(x, y)` cartesian coordinate pair
-#[generic_array_struct]
+#[generic_array_struct(ops)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct CartesianInner<T> {
@@ -65,3 +65,54 @@ pub mod inner {
         pub y: T,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: Cartesian = CartesianInner([1.0, 2.0]);
+    const B: Cartesian = CartesianInner([4.0, -8.0]);
+
+    #[test]
+    fn ops_elementwise() {
+        assert_eq!(A + B, CartesianInner([5.0, -6.0]));
+        assert_eq!(A - B, CartesianInner([-3.0, 10.0]));
+        assert_eq!(A * B, CartesianInner([4.0, -16.0]));
+        assert_eq!(B / A, CartesianInner([4.0, -4.0]));
+        assert_eq!(B % A, CartesianInner([0.0, -0.0]));
+        assert_eq!(-A, CartesianInner([-1.0, -2.0]));
+    }
+
+    // the by-reference impls are what's being tested
+    #[allow(clippy::op_ref)]
+    #[test]
+    fn ops_by_ref() {
+        assert_eq!(&A + &B, A + B);
+        assert_eq!(A - &B, A - B);
+        assert_eq!(&A * B, A * B);
+        assert_eq!(&B / 2.0, CartesianInner([2.0, -4.0]));
+        assert_eq!(-&A, -A);
+    }
+
+    #[test]
+    fn ops_scalar() {
+        assert_eq!(A * 3.0, CartesianInner([3.0, 6.0]));
+        assert_eq!(A + 1.0, CartesianInner([2.0, 3.0]));
+        assert_eq!(CartesianInner([7u8, 9]) % 4, CartesianInner([3, 1]));
+    }
+
+    #[test]
+    fn ops_assign() {
+        let mut c = A;
+        c += B;
+        assert_eq!(c, A + B);
+        c -= &B;
+        assert_eq!(c, A);
+        c *= 2.0;
+        assert_eq!(c, CartesianInner([2.0, 4.0]));
+        c /= A;
+        assert_eq!(c, CartesianInner([2.0, 2.0]));
+        c %= 2.0;
+        assert_eq!(c, Cartesian::ORIGIN);
+    }
+}
@@ -3,7 +3,7 @@
 use generic_array_struct::generic_array_struct;
 
 /// A RGB color triple
-#[generic_array_struct(all pub)]
+#[generic_array_struct(all mask pub)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct Rgb<T> {
@@ -114,6 +114,39 @@ mod tests {
         eprintln!("{yellow:#?} {purple:#?}");
     }
 
+    #[test]
+    fn clone_builder_non_copy() {
+        let r = Rc::new(1);
+
+        let a = NewRgbBuilder::start().with_r(r.clone()).with_b(r.clone());
+        let b = a.clone();
+        assert_eq!(Rc::strong_count(&r), 5);
+
+        drop(a);
+        assert_eq!(Rc::strong_count(&r), 3);
+
+        // the unset g slot must not have been cloned or dropped
+        let c = b.with_g(r.clone()).build();
+        assert_eq!(Rc::strong_count(&r), 4);
+
+        drop(c);
+        assert_eq!(Rc::strong_count(&r), 1);
+    }
+
+    #[test]
+    fn builder_eq() {
+        let a = NewRgbBuilder::start().with_r(255u8).with_b(0);
+        assert_eq!(a, a.clone());
+        assert_ne!(a, NewRgbBuilder::start().with_r(254u8).with_b(0));
+    }
+
+    #[test]
+    fn builder_debug() {
+        let a = NewRgbBuilder::start().with_r(255u8).with_b(0);
+        assert_eq!(format!("{a:?}"), "RgbBuilder { r: 255, g: <unset>, b: 0 }");
+        eprintln!("{a:#?}");
+    }
+
     #[test]
     fn destr_debug() {
         eprintln!("{:#?}", BLACK.const_into_destr());
@@ -197,6 +230,30 @@ mod tests {
         assert_eq!(SRC.try_map_res(f_res_id).unwrap(), SRC);
     }
 
+    #[test]
+    fn mem_safety_transpose_fail() {
+        // the Rcs before and after the failing field must be dropped exactly once
+        let r = Rc::new(1);
+
+        let opts = Rgb([Some(r.clone()), None, Some(r.clone())]);
+        assert_eq!(Rc::strong_count(&r), 3);
+        assert_eq!(opts.transpose(), None);
+        assert_eq!(Rc::strong_count(&r), 1);
+
+        let res: Rgb<Result<Rc<i32>, u8>> = Rgb([Ok(r.clone()), Err(1), Ok(r.clone())]);
+        assert_eq!(Rc::strong_count(&r), 3);
+        assert_eq!(res.transpose(), Err(1));
+        assert_eq!(Rc::strong_count(&r), 1);
+    }
+
+    #[test]
+    fn transpose_basic() {
+        const SRC: Rgb<u8> = Rgb([1, 2, 3]);
+
+        assert_eq!(Rgb(SRC.0.map(Some)).transpose(), Some(SRC));
+        assert_eq!(Rgb([Ok::<_, ()>(1), Ok(2), Ok(3)]).transpose(), Ok(SRC));
+    }
+
     #[test]
     fn zip_unzip_basic() {
         const T: Rgb<u8> = Rgb([0, 1, 2]);
@@ -216,4 +273,62 @@ mod tests {
         assert_eq!(TU_UNZ, (T, U));
         assert_eq!(TU_UNZ, TU.unzip());
     }
+
+    #[test]
+    fn zip_iters_unzip_iter() {
+        let planar: Rgb<Vec<u8>> = Rgb([vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+
+        let pixels: Vec<Rgb<u8>> = planar.clone().zip_iters().collect();
+        assert_eq!(pixels, [Rgb([1, 4, 7]), Rgb([2, 5, 8]), Rgb([3, 6, 9])]);
+        assert_eq!(Rgb::<Vec<u8>>::unzip_iter(pixels), planar);
+
+        // by reference, stopping at the shortest field
+        let short = Rgb([&planar.r()[..], &planar.g()[..1], &planar.b()[..]]);
+        assert_eq!(short.zip_iters().collect::<Vec<_>>(), [Rgb([&1, &4, &7])]);
+
+        let empty: Rgb<Vec<u8>> = Rgb::unzip_iter(core::iter::empty::<RgbU8>());
+        assert_eq!(empty, Rgb([vec![], vec![], vec![]]));
+    }
+
+    #[test]
+    fn mask_reductions() {
+        let m = Rgb([true, false, true]);
+        assert!(m.any());
+        assert!(!m.all());
+        assert_eq!(m.count(), 2);
+        assert!(!Rgb([false; 3]).any());
+        assert!(Rgb([true; 3]).all());
+    }
+
+    #[test]
+    fn mask_bits() {
+        const M: Rgb<bool> = Rgb::from_bits(0b1111_0110);
+        assert_eq!(M, Rgb([false, true, true]));
+        assert_eq!(M.to_bits(), 0b110);
+        for bits in 0..8u8 {
+            assert_eq!(Rgb::from_bits(bits).to_bits(), bits);
+        }
+    }
+
+    #[test]
+    fn mask_select_cmp() {
+        let a = RgbU8::red();
+        let b = Rgb([128, 128, 128]);
+        let brighter = a.gt_mask(&b);
+        assert_eq!(brighter, Rgb([true, false, false]));
+        assert_eq!(brighter.select(a, b), Rgb([255, 128, 128]));
+        assert_eq!(a.le_mask(&b), Rgb([false, true, true]));
+        assert_eq!(a.lt_mask(&a), Rgb([false; 3]));
+        assert_eq!(a.ge_mask(&a), Rgb([true; 3]));
+        assert_eq!(a.eq_mask(&BLACK), Rgb([false, true, true]));
+        assert_eq!(a.ne_mask(&BLACK).to_bits(), 0b001);
+
+        // non-Copy
+        let names = Rgb(["r", "g", "b"].map(String::from));
+        let empty = Rgb([(); 3].map(|_| String::new()));
+        assert_eq!(
+            Rgb([false, true, false]).select(names, empty).0,
+            ["", "g", ""]
+        );
+    }
 }
@@ -0,0 +1,246 @@
+#![doc = include_str!("../README.md")]
+#![no_std]
+
+use core::{
+    convert::Infallible,
+    fmt::{Debug, Display},
+    ops::ControlFlow,
+    task::Poll,
+};
+
+/// Error returned by checked arithmetic when the result for `field` overflows,
+/// or when `field` is divided by zero.
+///
+/// `F` is the field enum of the struct e.g. `RgbField`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OverflowAt<F> {
+    /// The first field, in array index order, whose result overflowed
+    /// or that was divided by zero
+    pub field: F,
+}
+
+impl<F: Display> Display for OverflowAt<F> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "arithmetic overflow at field `{}`", self.field)
+    }
+}
+
+impl<F: Debug + Display> core::error::Error for OverflowAt<F> {}
+
+/// Error returned by fallible mappings when the closure fails for `field`.
+///
+/// `F` is the field enum of the struct e.g. `RgbField`,
+/// which also provides the field's name and array index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldError<F, E> {
+    /// The first field, in array index order, that the closure failed for
+    pub field: F,
+
+    /// The error returned by the closure
+    pub error: E,
+}
+
+impl<F: Display, E: Display> Display for FieldError<F, E> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "error at field `{}`: {}", self.field, self.error)
+    }
+}
+
+impl<F: Debug + Display, E: core::error::Error + 'static> core::error::Error for FieldError<F, E> {
+    #[inline]
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Stable stand-in for the unstable `Try` trait, used by `trymap(generic)`'s `try_map`
+/// to short-circuit on the first field that fails.
+///
+/// Implement this for your own result-like types to use them with `try_map`.
+pub trait TryLike: Sized {
+    /// The value produced when not short-circuiting e.g. `T` in `Option<T>`
+    type Output;
+
+    /// The value propagated when short-circuiting e.g. `E` in `Result<T, E>`
+    type Residual;
+
+    /// The same type with a different [`TryLike::Output`] and the same [`TryLike::Residual`]
+    /// e.g. `Result<O, E>` for `Result<T, E>`
+    type WithOutput<O>: TryLike<Output = O, Residual = Self::Residual>;
+
+    /// Whether to continue with the output or short-circuit with the residual
+    fn branch(self) -> ControlFlow<Self::Residual, Self::Output>;
+
+    /// Wraps a value that did not short-circuit e.g. `Some`
+    fn from_output(output: Self::Output) -> Self;
+
+    /// Wraps a short-circuited residual e.g. `Err`
+    fn from_residual(residual: Self::Residual) -> Self;
+}
+
+impl<T> TryLike for Option<T> {
+    type Output = T;
+    type Residual = Option<Infallible>;
+    type WithOutput<O> = Option<O>;
+
+    #[inline]
+    fn branch(self) -> ControlFlow<Self::Residual, T> {
+        match self {
+            Some(t) => ControlFlow::Continue(t),
+            None => ControlFlow::Break(None),
+        }
+    }
+
+    #[inline]
+    fn from_output(output: T) -> Self {
+        Some(output)
+    }
+
+    #[inline]
+    fn from_residual(_residual: Self::Residual) -> Self {
+        None
+    }
+}
+
+impl<T, E> TryLike for Result<T, E> {
+    type Output = T;
+    type Residual = Result<Infallible, E>;
+    type WithOutput<O> = Result<O, E>;
+
+    #[inline]
+    fn branch(self) -> ControlFlow<Self::Residual, T> {
+        match self {
+            Ok(t) => ControlFlow::Continue(t),
+            Err(e) => ControlFlow::Break(Err(e)),
+        }
+    }
+
+    #[inline]
+    fn from_output(output: T) -> Self {
+        Ok(output)
+    }
+
+    #[inline]
+    fn from_residual(residual: Self::Residual) -> Self {
+        match residual {
+            Err(e) => Err(e),
+            Ok(never) => match never {},
+        }
+    }
+}
+
+impl<B, C> TryLike for ControlFlow<B, C> {
+    type Output = C;
+    type Residual = ControlFlow<B, Infallible>;
+    type WithOutput<O> = ControlFlow<B, O>;
+
+    #[inline]
+    fn branch(self) -> ControlFlow<Self::Residual, C> {
+        match self {
+            Self::Continue(c) => ControlFlow::Continue(c),
+            Self::Break(b) => ControlFlow::Break(ControlFlow::Break(b)),
+        }
+    }
+
+    #[inline]
+    fn from_output(output: C) -> Self {
+        Self::Continue(output)
+    }
+
+    #[inline]
+    fn from_residual(residual: Self::Residual) -> Self {
+        match residual {
+            ControlFlow::Break(b) => Self::Break(b),
+            ControlFlow::Continue(never) => match never {},
+        }
+    }
+}
+
+/// Same as `?` on `Poll<Result<T, E>>`:
+/// short-circuits on `Ready(Err(_))`, and `Pending` is not short-circuited but output as is.
+///
+/// Since the output is a `Poll<T>`, [`TryLike::WithOutput`] is a `Result`,
+/// e.g. mapping to `Poll<Result<T, E>>` produces a `Result<Rgb<Poll<T>>, E>`.
+impl<T, E> TryLike for Poll<Result<T, E>> {
+    type Output = Poll<T>;
+    type Residual = Result<Infallible, E>;
+    type WithOutput<O> = Result<O, E>;
+
+    #[inline]
+    fn branch(self) -> ControlFlow<Self::Residual, Poll<T>> {
+        match self {
+            Poll::Ready(Ok(t)) => ControlFlow::Continue(Poll::Ready(t)),
+            Poll::Ready(Err(e)) => ControlFlow::Break(Err(e)),
+            Poll::Pending => ControlFlow::Continue(Poll::Pending),
+        }
+    }
+
+    #[inline]
+    fn from_output(output: Poll<T>) -> Self {
+        output.map(Ok)
+    }
+
+    #[inline]
+    fn from_residual(residual: Self::Residual) -> Self {
+        Poll::Ready(Result::from_residual(residual))
+    }
+}
+
+/// Implemented by structs with the `transpose` arg, e.g. `Rgb<T>`,
+/// to convert between them generically.
+pub trait GenericArrayStruct: Sized {
+    /// The type of the array elements e.g. `T` in `Rgb<T>`
+    type Elem;
+
+    /// The same struct with a different element type e.g. `Rgb<U>`
+    type WithElem<U>: GenericArrayStruct<Elem = U>;
+
+    /// Iterator over the array elements, in array index order
+    type IntoElems: Iterator<Item = Self::Elem>;
+
+    /// `f` is called with each array index in order.
+    ///
+    /// With the `ctor` arg, this is shadowed by the inherent `from_fn()`,
+    /// which takes the field enum instead, so call it with e.g.
+    /// `<Rgb<T> as GenericArrayStruct>::from_fn(f)`
+    fn from_fn<F: FnMut(usize) -> Self::Elem>(f: F) -> Self;
+
+    /// Consumes `self`, returning the array elements in array index order
+    fn into_elems(self) -> Self::IntoElems;
+
+    /// The array elements, in array index order
+    fn as_mut_slice(&mut self) -> &mut [Self::Elem];
+
+    /// Elementwise map, in array index order
+    fn map<U, F: FnMut(Self::Elem) -> U>(self, f: F) -> Self::WithElem<U>;
+}
+
+/// Swaps the nesting of 2 [`GenericArrayStruct`]s e.g. `Xy<Rgb<T>>` into `Rgb<Xy<T>>`
+pub trait Transpose {
+    /// e.g. `Rgb<Xy<T>>` for `Xy<Rgb<T>>`
+    type Output;
+
+    /// Field `i` of field `o` of `self` becomes field `o` of field `i` of the output
+    fn transpose(self) -> Self::Output;
+}
+
+impl<O, I> Transpose for O
+where
+    O: GenericArrayStruct<Elem = I>,
+    I: GenericArrayStruct,
+{
+    type Output = I::WithElem<O::WithElem<I::Elem>>;
+
+    #[inline]
+    fn transpose(self) -> Self::Output {
+        let mut inners = self.map(I::into_elems);
+        GenericArrayStruct::from_fn(|_| {
+            GenericArrayStruct::from_fn(|o| match inners.as_mut_slice()[o].next() {
+                Some(elem) => elem,
+                None => unreachable!("all inner structs have the same number of elements"),
+            })
+        })
+    }
+}